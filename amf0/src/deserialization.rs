@@ -25,6 +25,12 @@ pub fn deserialize<R: Read>(bytes: &mut R) -> Result<Vec<Amf0Value>, Deserializa
     Ok(results)
 }
 
+// Reads exactly one AMF0 value from a byte stream, leaving the stream positioned directly after it
+pub fn deserialize_value<R: Read>(bytes: &mut R) -> Result<Amf0Value, DeserializationError> {
+    let mut references = vec![];
+    read_next_value(bytes, &mut references)?.ok_or(DeserializationError::UnexpectedEof)
+}
+
 fn read_next_value<R: Read>(
     bytes: &mut R,
     references: &mut Vec<Amf0Value>,
@@ -190,6 +196,7 @@ mod tests {
 
     use super::super::Amf0Value;
     use super::deserialize;
+    use super::deserialize_value;
     use super::markers;
     use std::collections::HashMap;
     use std::io::Cursor;
@@ -336,6 +343,21 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn can_deserialize_single_value() {
+        let mut vector = vec![];
+        vector.write_u8(markers::NUMBER_MARKER).unwrap();
+        vector.write_f64_be(1.0).unwrap();
+        vector.write_u8(markers::BOOLEAN_MARKER).unwrap();
+        vector.write_u8(1).unwrap();
+
+        let mut input = Cursor::new(vector);
+        let result = deserialize_value(&mut input).unwrap();
+
+        assert_eq!(result, Amf0Value::Number(1.0));
+        assert_eq!(input.position(), 9);
+    }
+
     #[test]
     fn can_deserialize_undefined() {
         let mut vector = vec![];
@@ -6,7 +6,7 @@ mod deserialization;
 mod error;
 mod serialization;
 
-pub use deserialization::deserialize;
+pub use deserialization::{deserialize, deserialize_value};
 pub use error::{DeserializationError, SerializationError};
 pub use serialization::serialize;
 
@@ -1,7 +1,7 @@
 use crate::{Line, Point};
 use vector2d::Vector2Df;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rectangle {
     origin: Point,
     size: Vector2Df,
@@ -76,6 +76,35 @@ impl Rectangle {
             && point.y() <= self.origin.y() + self.size.y()
     }
 
+    /** Whether this rectangle shares any area or edge with another rectangle */
+    pub fn overlaps(&self, other: &Rectangle) -> bool {
+        self.origin.x() <= other.origin.x() + other.size.x()
+            && other.origin.x() <= self.origin.x() + self.size.x()
+            && self.origin.y() <= other.origin.y() + other.size.y()
+            && other.origin.y() <= self.origin.y() + self.size.y()
+    }
+
+    /** Returns the smallest rectangle containing both this rectangle and a point */
+    pub fn expanded_to_include(&self, point: Point) -> Rectangle {
+        let top_right = self.top_right();
+        Rectangle::new(
+            Point::new(
+                self.origin.x().min(point.x()),
+                self.origin.y().min(point.y()),
+            ),
+            Point::new(top_right.x().max(point.x()), top_right.y().max(point.y())),
+        )
+    }
+
+    /** Returns this rectangle grown outwards by a margin on every side */
+    pub fn expanded_by(&self, margin: f64) -> Rectangle {
+        Rectangle::new(
+            self.origin.translated_by(Vector2Df::new(-margin, -margin)),
+            self.top_right()
+                .translated_by(Vector2Df::new(margin, margin)),
+        )
+    }
+
     /** Whether this rectangle includes part of a line, including lines with endpoints outside of the rectangle that intersect it */
     pub fn includes_portion_of_line(&self, line: &Line) -> bool {
         self.contains_point(line.p0())
@@ -127,6 +156,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn overlap() {
+        let rect = Rectangle::new(Point::new(-1.0, -3.0), Point::new(1.0, 3.0));
+        assert!(
+            rect.overlaps(&Rectangle::new(Point::new(0.0, 0.0), Point::new(5.0, 5.0))),
+            "rectangle should overlap rectangle sharing area"
+        );
+        assert!(
+            rect.overlaps(&Rectangle::new(Point::new(1.0, 3.0), Point::new(5.0, 5.0))),
+            "rectangle should overlap rectangle sharing a corner"
+        );
+        assert!(
+            rect.overlaps(&Rectangle::new(
+                Point::new(-5.0, -5.0),
+                Point::new(5.0, 5.0)
+            )),
+            "rectangle should overlap rectangle containing it"
+        );
+        assert!(
+            !rect.overlaps(&Rectangle::new(Point::new(2.0, 0.0), Point::new(5.0, 5.0))),
+            "rectangle should not overlap rectangle beside it"
+        );
+    }
+
+    #[test]
+    fn expansion() {
+        let rect = Rectangle::new(Point::new(-1.0, -3.0), Point::new(1.0, 3.0));
+        assert!(
+            rect.expanded_to_include(Point::new(4.0, 0.0))
+                == Rectangle::new(Point::new(-1.0, -3.0), Point::new(4.0, 3.0)),
+            "rectangle should grow to include point outside"
+        );
+        assert!(
+            rect.expanded_to_include(Point::zero()) == rect,
+            "rectangle should not change for point inside"
+        );
+        assert!(
+            rect.expanded_by(2.0) == Rectangle::new(Point::new(-3.0, -5.0), Point::new(3.0, 5.0)),
+            "rectangle should grow by margin on every side"
+        );
+    }
+
     #[test]
     fn line_inclusion() {
         let rect = Rectangle::new(Point::new(-1.0, -3.0), Point::new(1.0, 3.0));
@@ -6,6 +6,7 @@ mod rider;
 mod scenery_line;
 mod standard_line;
 mod track;
+mod trigger;
 pub mod unit_conversion;
 
 pub use grid_version::GridVersion;
@@ -16,3 +17,4 @@ pub use rider::{Rider, RiderBuilder};
 pub use scenery_line::{SceneryLine, SceneryLineBuilder};
 pub use standard_line::{StandardLine, StandardLineBuilder};
 pub use track::{Track, TrackBuilder};
+pub use trigger::{Trigger, TriggerBuilder, TriggerEvent};
@@ -6,6 +6,7 @@ use crate::RemountVersion;
 pub struct Rider {
     start_offset: Option<Vector2Df>,
     start_velocity: Option<Vector2Df>,
+    start_angle: Option<f64>,
     remount_version: RemountVersion,
 }
 
@@ -18,6 +19,11 @@ impl Rider {
         self.start_velocity
     }
 
+    /// Initial rotation of the rider in degrees
+    pub fn start_angle(&self) -> Option<f64> {
+        self.start_angle
+    }
+
     pub fn remount_version(&self) -> RemountVersion {
         self.remount_version
     }
@@ -27,6 +33,7 @@ impl Rider {
 pub struct RiderBuilder {
     start_offset: Option<Vector2Df>,
     start_velocity: Option<Vector2Df>,
+    start_angle: Option<f64>,
     remount_version: RemountVersion,
 }
 
@@ -35,6 +42,7 @@ impl RiderBuilder {
         Self {
             start_offset: None,
             start_velocity: None,
+            start_angle: None,
             remount_version,
         }
     }
@@ -49,6 +57,11 @@ impl RiderBuilder {
         self
     }
 
+    pub fn start_angle(&mut self, start_angle: f64) -> &mut Self {
+        self.start_angle = Some(start_angle);
+        self
+    }
+
     pub fn remount_version(&mut self, remount_version: RemountVersion) -> &mut Self {
         self.remount_version = remount_version;
         self
@@ -58,6 +71,7 @@ impl RiderBuilder {
         Rider {
             start_offset: self.start_offset,
             start_velocity: self.start_velocity,
+            start_angle: self.start_angle,
             remount_version: self.remount_version,
         }
     }
@@ -68,6 +82,7 @@ impl From<Rider> for RiderBuilder {
         RiderBuilder {
             start_offset: rider.start_offset,
             start_velocity: rider.start_velocity,
+            start_angle: rider.start_angle,
             remount_version: rider.remount_version,
         }
     }
@@ -1,6 +1,9 @@
+use color::RGBColor;
+use vector2d::Vector2Df;
+
 use crate::{
     GridVersion, Layer, LayerBuilder, LayerFolder, LayerFolderBuilder, Rider, RiderBuilder,
-    SceneryLine, SceneryLineBuilder, StandardLine, StandardLineBuilder,
+    SceneryLine, SceneryLineBuilder, StandardLine, StandardLineBuilder, Trigger, TriggerBuilder,
 };
 
 #[derive(Debug, PartialEq)]
@@ -12,11 +15,18 @@ pub struct Track {
     duration: Option<u32>,
     audio_filename: Option<String>,
     audio_offset: Option<f64>, // Offset (in seconds) until the song starts
+    start_zoom: Option<f64>,
+    start_gravity: Option<Vector2Df>,
+    gravity_well_size: Option<f64>,
+    frictionless: bool, // Whether contact points slide along lines without friction
+    start_background_color: Option<RGBColor>,
+    start_line_color: Option<RGBColor>,
     standard_lines: Vec<StandardLine>,
     scenery_lines: Vec<SceneryLine>,
     layers: Vec<Layer>,
     layer_folders: Vec<LayerFolder>,
     riders: Vec<Rider>,
+    triggers: Vec<Trigger>,
 }
 
 impl Track {
@@ -48,6 +58,30 @@ impl Track {
         self.audio_offset
     }
 
+    pub fn start_zoom(&self) -> Option<f64> {
+        self.start_zoom
+    }
+
+    pub fn start_gravity(&self) -> Option<Vector2Df> {
+        self.start_gravity
+    }
+
+    pub fn gravity_well_size(&self) -> Option<f64> {
+        self.gravity_well_size
+    }
+
+    pub fn frictionless(&self) -> bool {
+        self.frictionless
+    }
+
+    pub fn start_background_color(&self) -> Option<RGBColor> {
+        self.start_background_color
+    }
+
+    pub fn start_line_color(&self) -> Option<RGBColor> {
+        self.start_line_color
+    }
+
     pub fn standard_lines(&self) -> &Vec<StandardLine> {
         &self.standard_lines
     }
@@ -67,6 +101,10 @@ impl Track {
     pub fn riders(&self) -> &Vec<Rider> {
         &self.riders
     }
+
+    pub fn triggers(&self) -> &Vec<Trigger> {
+        &self.triggers
+    }
 }
 
 pub struct TrackBuilder {
@@ -77,11 +115,18 @@ pub struct TrackBuilder {
     duration: Option<u32>,
     audio_filename: Option<String>,
     audio_offset: Option<f64>, // Offset (in seconds) until the song starts
+    start_zoom: Option<f64>,
+    start_gravity: Option<Vector2Df>,
+    gravity_well_size: Option<f64>,
+    frictionless: bool, // Whether contact points slide along lines without friction
+    start_background_color: Option<RGBColor>,
+    start_line_color: Option<RGBColor>,
     standard_lines: Vec<StandardLineBuilder>,
     scenery_lines: Vec<SceneryLineBuilder>,
     layers: Vec<LayerBuilder>,
     layer_folders: Vec<LayerFolderBuilder>,
     riders: Vec<RiderBuilder>,
+    triggers: Vec<TriggerBuilder>,
 }
 
 impl TrackBuilder {
@@ -94,11 +139,18 @@ impl TrackBuilder {
             duration: None,
             audio_filename: None,
             audio_offset: None,
+            start_zoom: None,
+            start_gravity: None,
+            gravity_well_size: None,
+            frictionless: false,
+            start_background_color: None,
+            start_line_color: None,
             standard_lines: Vec::new(),
             scenery_lines: Vec::new(),
             layers: Vec::new(),
             layer_folders: Vec::new(),
             riders: Vec::new(),
+            triggers: Vec::new(),
         }
     }
 
@@ -137,6 +189,36 @@ impl TrackBuilder {
         self
     }
 
+    pub fn start_zoom(&mut self, start_zoom: f64) -> &mut Self {
+        self.start_zoom = Some(start_zoom);
+        self
+    }
+
+    pub fn start_gravity(&mut self, start_gravity: Vector2Df) -> &mut Self {
+        self.start_gravity = Some(start_gravity);
+        self
+    }
+
+    pub fn gravity_well_size(&mut self, gravity_well_size: f64) -> &mut Self {
+        self.gravity_well_size = Some(gravity_well_size);
+        self
+    }
+
+    pub fn frictionless(&mut self, frictionless: bool) -> &mut Self {
+        self.frictionless = frictionless;
+        self
+    }
+
+    pub fn start_background_color(&mut self, start_background_color: RGBColor) -> &mut Self {
+        self.start_background_color = Some(start_background_color);
+        self
+    }
+
+    pub fn start_line_color(&mut self, start_line_color: RGBColor) -> &mut Self {
+        self.start_line_color = Some(start_line_color);
+        self
+    }
+
     pub fn standard_lines(&mut self) -> &mut Vec<StandardLineBuilder> {
         &mut self.standard_lines
     }
@@ -157,6 +239,10 @@ impl TrackBuilder {
         &mut self.riders
     }
 
+    pub fn triggers(&mut self) -> &mut Vec<TriggerBuilder> {
+        &mut self.triggers
+    }
+
     pub fn build(self) -> Track {
         Track {
             grid_version: self.grid_version,
@@ -166,11 +252,18 @@ impl TrackBuilder {
             duration: self.duration,
             audio_filename: self.audio_filename,
             audio_offset: self.audio_offset,
+            start_zoom: self.start_zoom,
+            start_gravity: self.start_gravity,
+            gravity_well_size: self.gravity_well_size,
+            frictionless: self.frictionless,
+            start_background_color: self.start_background_color,
+            start_line_color: self.start_line_color,
             standard_lines: self.standard_lines.into_iter().map(|x| x.build()).collect(),
             scenery_lines: self.scenery_lines.into_iter().map(|x| x.build()).collect(),
             layers: self.layers.into_iter().map(|x| x.build()).collect(),
             layer_folders: self.layer_folders.into_iter().map(|x| x.build()).collect(),
             riders: self.riders.into_iter().map(|x| x.build()).collect(),
+            triggers: self.triggers.into_iter().map(|x| x.build()).collect(),
         }
     }
 }
@@ -185,11 +278,18 @@ impl From<Track> for TrackBuilder {
             duration: track.duration,
             audio_filename: track.audio_filename,
             audio_offset: track.audio_offset,
+            start_zoom: track.start_zoom,
+            start_gravity: track.start_gravity,
+            gravity_well_size: track.gravity_well_size,
+            frictionless: track.frictionless,
+            start_background_color: track.start_background_color,
+            start_line_color: track.start_line_color,
             standard_lines: track.standard_lines.into_iter().map(|x| x.into()).collect(),
             scenery_lines: track.scenery_lines.into_iter().map(|x| x.into()).collect(),
             layers: track.layers.into_iter().map(|x| x.into()).collect(),
             layer_folders: track.layer_folders.into_iter().map(|x| x.into()).collect(),
             riders: track.riders.into_iter().map(|x| x.into()).collect(),
+            triggers: track.triggers.into_iter().map(|x| x.into()).collect(),
         }
     }
 }
@@ -0,0 +1,78 @@
+use color::RGBColor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerEvent {
+    Zoom(f64),
+    BackgroundColor(RGBColor),
+    LineColor(RGBColor),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Trigger {
+    start_frame: u32,
+    end_frame: u32,
+    event: TriggerEvent,
+}
+
+impl Trigger {
+    pub fn start_frame(&self) -> u32 {
+        self.start_frame
+    }
+
+    pub fn end_frame(&self) -> u32 {
+        self.end_frame
+    }
+
+    pub fn event(&self) -> TriggerEvent {
+        self.event
+    }
+}
+
+pub struct TriggerBuilder {
+    start_frame: u32,
+    end_frame: u32,
+    event: TriggerEvent,
+}
+
+impl TriggerBuilder {
+    pub fn new(event: TriggerEvent, start_frame: u32, end_frame: u32) -> Self {
+        Self {
+            start_frame,
+            end_frame,
+            event,
+        }
+    }
+
+    pub fn start_frame(&mut self, start_frame: u32) -> &mut Self {
+        self.start_frame = start_frame;
+        self
+    }
+
+    pub fn end_frame(&mut self, end_frame: u32) -> &mut Self {
+        self.end_frame = end_frame;
+        self
+    }
+
+    pub fn event(&mut self, event: TriggerEvent) -> &mut Self {
+        self.event = event;
+        self
+    }
+
+    pub fn build(self) -> Trigger {
+        Trigger {
+            start_frame: self.start_frame,
+            end_frame: self.end_frame,
+            event: self.event,
+        }
+    }
+}
+
+impl From<Trigger> for TriggerBuilder {
+    fn from(trigger: Trigger) -> Self {
+        TriggerBuilder {
+            start_frame: trigger.start_frame,
+            end_frame: trigger.end_frame,
+            event: trigger.event,
+        }
+    }
+}
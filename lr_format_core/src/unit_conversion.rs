@@ -19,3 +19,21 @@ pub fn from_web_gravity(gravity: Vector2Df) -> Vector2Df {
 pub fn from_lra_audio_offset(offset: f64) -> f64 {
     -offset
 }
+
+#[expect(clippy::cast_possible_truncation)]
+pub fn to_lra_zoom(zoom: f64) -> f32 {
+    f64::powf(2.0, zoom) as f32
+}
+
+pub fn to_lra_gravity(gravity: Vector2Df) -> Vector2Df {
+    gravity.flipped_vertical() / 0.175
+}
+
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn to_lra_scenery_width(width: f64) -> u8 {
+    (width * 10.0).round() as u8
+}
+
+pub fn to_lra_audio_offset(offset: f64) -> f64 {
+    -offset
+}
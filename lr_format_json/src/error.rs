@@ -47,3 +47,47 @@ impl From<str::Utf8Error> for JsonReadError {
         JsonReadError::Other(Box::new(value))
     }
 }
+
+#[derive(Debug)]
+pub enum JsonWriteError {
+    UnsupportedRemountVersion(String),
+    UnsupportedStartVelocity(String),
+    UnsupportedStartAngle(String),
+    InconsistentLineHeight(String),
+    InconsistentSceneryWidth(String),
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for JsonWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::UnsupportedRemountVersion(e) => write!(f, "Unsupported remount version: {}", e),
+            Self::UnsupportedStartVelocity(e) => write!(f, "Unsupported start velocity: {}", e),
+            Self::UnsupportedStartAngle(e) => write!(f, "Unsupported start angle: {}", e),
+            Self::InconsistentLineHeight(e) => write!(f, "Inconsistent line height: {}", e),
+            Self::InconsistentSceneryWidth(e) => write!(f, "Inconsistent scenery width: {}", e),
+            Self::Other(e) => write!(f, "Other error occurred: {}", e),
+        }
+    }
+}
+
+impl Error for JsonWriteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            JsonWriteError::Other(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonWriteError {
+    fn from(value: serde_json::Error) -> Self {
+        JsonWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<num::TryFromIntError> for JsonWriteError {
+    fn from(value: num::TryFromIntError) -> Self {
+        JsonWriteError::Other(Box::new(value))
+    }
+}
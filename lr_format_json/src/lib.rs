@@ -3,9 +3,11 @@
 mod error;
 mod json_array_line;
 mod reader;
+mod writer;
 
-pub use error::JsonReadError;
+pub use error::{JsonReadError, JsonWriteError};
 pub use reader::read;
+pub use writer::write;
 
 use serde::{Deserialize, Serialize};
 
@@ -118,10 +120,11 @@ struct JsonLine {
 #[derive(Serialize, Deserialize, Debug)]
 struct JsonLayer {
     id: u32,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     layer_type: Option<u8>,
     name: String,
     visible: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     editable: Option<bool>,
     #[serde(rename = "folderId", skip_serializing_if = "Option::is_none")]
     folder_id: Option<FaultyU32>, // -1 if not a folder id
@@ -143,16 +146,24 @@ struct JsonRider {
 
 #[derive(Serialize, Deserialize, Debug)]
 struct JsonTrack {
+    #[serde(skip_serializing_if = "Option::is_none")]
     label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<u32>,
     version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     lines: Option<Vec<JsonLine>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     layers: Option<Vec<JsonLayer>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     riders: Option<Vec<JsonRider>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     script: Option<String>, // Unused
-    #[serde(rename = "startPosition")]
+    #[serde(rename = "startPosition", skip_serializing_if = "Option::is_none")]
     start_pos: Option<V2>,
     #[serde(rename = "linesArray", skip_serializing_if = "Option::is_none")]
     line_array: Option<Vec<LRAJsonArrayLine>>,
@@ -3,7 +3,7 @@ use color::RGBColor;
 use geometry::{Line, Point};
 use lr_format_core::{
     GridVersion, LayerBuilder, LayerFolderBuilder, RemountVersion, RiderBuilder,
-    SceneryLineBuilder, StandardLineBuilder, Track, TrackBuilder,
+    SceneryLineBuilder, StandardLineBuilder, Track, TrackBuilder, TriggerBuilder, TriggerEvent,
     unit_conversion::{from_lra_gravity, from_lra_zoom},
 };
 use vector2d::Vector2Df;
@@ -62,12 +62,6 @@ pub fn read(bytes: &[u8]) -> Result<Track, JsonReadError> {
                 0.0
             };
 
-            let width = if line_type == LineType::Scenery {
-                line.width.unwrap_or(1.0)
-            } else {
-                1.0
-            };
-
             if line_type != LineType::Scenery {
                 let mut standard_line = StandardLineBuilder::new(endpoints);
                 standard_line.flipped(flipped);
@@ -77,7 +71,7 @@ pub fn read(bytes: &[u8]) -> Result<Track, JsonReadError> {
                 ordered_standard_lines.push((line.id, standard_line));
             } else {
                 let mut scenery_line = SceneryLineBuilder::new(endpoints);
-                scenery_line.width(width);
+                scenery_line.width(line.width.unwrap_or(1.0));
                 track.scenery_lines().push(scenery_line);
             }
         }
@@ -210,6 +204,10 @@ pub fn read(bytes: &[u8]) -> Result<Track, JsonReadError> {
             rider.start_offset(start_position + rider_global_offset);
             rider.start_velocity(start_velocity);
 
+            if let Some(angle) = json_rider.angle {
+                rider.start_angle(angle);
+            }
+
             if let Some(remount) = &json_rider.remountable {
                 let (remount_bool, remount_version) = match remount {
                     FaultyBool::BoolRep(x) => (*x, RemountVersion::ComV1),
@@ -252,32 +250,46 @@ pub fn read(bytes: &[u8]) -> Result<Track, JsonReadError> {
     }
 
     if let Some(gravity_well_size) = json_track.gravity_well_size {
+        track.gravity_well_size(gravity_well_size);
         for line in track.standard_lines() {
             line.height(gravity_well_size);
         }
     }
 
-    let _start_zoom = json_track
-        .start_zoom
-        .map(f64::from)
-        .unwrap_or(from_lra_zoom(4.0));
+    if let Some(start_zoom) = json_track.start_zoom {
+        track.start_zoom(from_lra_zoom(start_zoom));
+    }
 
-    let _start_gravity = from_lra_gravity(Vector2Df::new(
-        f64::from(json_track.start_gravity_x.unwrap_or(0.0)),
-        f64::from(json_track.start_gravity_y.unwrap_or(1.0)),
-    ));
+    // Default values assigned because LRO doesn't write every component (same as the trk format)
 
-    let _start_background_color = RGBColor::new(
-        u8::try_from(json_track.start_bg_color_red.unwrap_or(244))?,
-        u8::try_from(json_track.start_bg_color_green.unwrap_or(245))?,
-        u8::try_from(json_track.start_bg_color_blue.unwrap_or(249))?,
-    );
+    if json_track.start_gravity_x.is_some() || json_track.start_gravity_y.is_some() {
+        track.start_gravity(from_lra_gravity(Vector2Df::new(
+            f64::from(json_track.start_gravity_x.unwrap_or(0.0)),
+            f64::from(json_track.start_gravity_y.unwrap_or(1.0)),
+        )));
+    }
 
-    let _start_line_color = RGBColor::new(
-        u8::try_from(json_track.start_line_color_red.unwrap_or(0))?,
-        u8::try_from(json_track.start_line_color_green.unwrap_or(0))?,
-        u8::try_from(json_track.start_line_color_blue.unwrap_or(0))?,
-    );
+    if json_track.start_bg_color_red.is_some()
+        || json_track.start_bg_color_green.is_some()
+        || json_track.start_bg_color_blue.is_some()
+    {
+        track.start_background_color(RGBColor::new(
+            u8::try_from(json_track.start_bg_color_red.unwrap_or(244))?,
+            u8::try_from(json_track.start_bg_color_green.unwrap_or(245))?,
+            u8::try_from(json_track.start_bg_color_blue.unwrap_or(249))?,
+        ));
+    }
+
+    if json_track.start_line_color_red.is_some()
+        || json_track.start_line_color_green.is_some()
+        || json_track.start_line_color_blue.is_some()
+    {
+        track.start_line_color(RGBColor::new(
+            u8::try_from(json_track.start_line_color_red.unwrap_or(0))?,
+            u8::try_from(json_track.start_line_color_green.unwrap_or(0))?,
+            u8::try_from(json_track.start_line_color_blue.unwrap_or(0))?,
+        ));
+    }
 
     if let Some(line_triggers) = json_track.line_based_triggers {
         for trigger in line_triggers {
@@ -292,40 +304,49 @@ pub fn read(bytes: &[u8]) -> Result<Track, JsonReadError> {
             match trigger.trigger_type {
                 0 => {
                     // Zoom
-                    let _target_zoom = from_lra_zoom(trigger.zoom_target);
-                    let _start_frame = trigger.start;
-                    let _end_frame = trigger.end;
+                    let target_zoom = from_lra_zoom(trigger.zoom_target);
+                    track.triggers().push(TriggerBuilder::new(
+                        TriggerEvent::Zoom(target_zoom),
+                        trigger.start,
+                        trigger.end,
+                    ));
                 }
                 1 => {
                     // Background Color
-                    let _red = u8::try_from(
+                    let red = u8::try_from(
                         Option::<u32>::from(trigger.background_red.ok_or_else(err)?)
                             .ok_or_else(err)?,
                     )?;
-                    let _green = u8::try_from(
+                    let green = u8::try_from(
                         Option::<u32>::from(trigger.background_green.ok_or_else(err)?)
                             .ok_or_else(err)?,
                     )?;
-                    let _blue = u8::try_from(
+                    let blue = u8::try_from(
                         Option::<u32>::from(trigger.background_blue.ok_or_else(err)?)
                             .ok_or_else(err)?,
                     )?;
-                    let _start_frame = trigger.start;
-                    let _end_frame = trigger.end;
+                    track.triggers().push(TriggerBuilder::new(
+                        TriggerEvent::BackgroundColor(RGBColor::new(red, green, blue)),
+                        trigger.start,
+                        trigger.end,
+                    ));
                 }
                 2 => {
                     // Line Color
-                    let _red = u8::try_from(
+                    let red = u8::try_from(
                         Option::<u32>::from(trigger.line_red.ok_or_else(err)?).ok_or_else(err)?,
                     )?;
-                    let _green = u8::try_from(
+                    let green = u8::try_from(
                         Option::<u32>::from(trigger.line_green.ok_or_else(err)?).ok_or_else(err)?,
                     )?;
-                    let _blue = u8::try_from(
+                    let blue = u8::try_from(
                         Option::<u32>::from(trigger.line_blue.ok_or_else(err)?).ok_or_else(err)?,
                     )?;
-                    let _start_frame = trigger.start;
-                    let _end_frame = trigger.end;
+                    track.triggers().push(TriggerBuilder::new(
+                        TriggerEvent::LineColor(RGBColor::new(red, green, blue)),
+                        trigger.start,
+                        trigger.end,
+                    ));
                 }
                 other => return Err(JsonReadError::UnsupportedTriggerType(other.to_string())),
             }
@@ -0,0 +1,311 @@
+use crate::{
+    FaultyBool, FaultyU32, JsonLayer, JsonLine, JsonRider, JsonTrack, JsonWriteError,
+    LRAJsonTrigger, V2, json_array_line::LRAJsonArrayLine,
+};
+use lr_format_core::{
+    GridVersion, Layer, LayerFolder, RemountVersion, Track, TriggerEvent,
+    unit_conversion::{to_lra_gravity, to_lra_zoom},
+};
+use vector2d::Vector2Df;
+
+pub fn write(track: &Track) -> Result<Vec<u8>, JsonWriteError> {
+    let version = match track.grid_version() {
+        GridVersion::V6_0 => "6.0",
+        GridVersion::V6_1 => "6.1",
+        GridVersion::V6_2 => "6.2",
+    };
+
+    let mut lines = Vec::new();
+    let mut line_id: u32 = 0;
+
+    // Ids are shared between line types, and the reader orders standard lines by id
+    for standard_line in track.standard_lines() {
+        let is_acceleration = standard_line.multiplier() != 0.0;
+        lines.push(JsonLine {
+            id: line_id,
+            line_type: if is_acceleration { 1 } else { 0 },
+            x1: standard_line.x0(),
+            y1: standard_line.y0(),
+            x2: standard_line.x1(),
+            y2: standard_line.y1(),
+            flipped: Some(FaultyBool::BoolRep(standard_line.flipped())),
+            left_ext: Some(FaultyBool::BoolRep(standard_line.left_extension())),
+            right_ext: Some(FaultyBool::BoolRep(standard_line.right_extension())),
+            extended: None,
+            multiplier: is_acceleration.then_some(standard_line.multiplier()),
+            width: None,
+        });
+        line_id += 1;
+    }
+
+    // Scenery lines without a width can only come from the legacy line array, which is read after every line object
+    let mut line_array = Vec::new();
+
+    for scenery_line in track.scenery_lines() {
+        match scenery_line.width() {
+            Some(width) => {
+                if !line_array.is_empty() {
+                    return Err(JsonWriteError::InconsistentSceneryWidth(format!(
+                        "scenery line {} has width {} after a scenery line without one",
+                        line_id, width
+                    )));
+                }
+
+                lines.push(JsonLine {
+                    id: line_id,
+                    line_type: 2,
+                    x1: scenery_line.x0(),
+                    y1: scenery_line.y0(),
+                    x2: scenery_line.x1(),
+                    y2: scenery_line.y1(),
+                    flipped: None,
+                    left_ext: None,
+                    right_ext: None,
+                    extended: None,
+                    multiplier: None,
+                    width: Some(width),
+                });
+            }
+            None => {
+                line_array.push(LRAJsonArrayLine::Scenery(
+                    FaultyU32::Valid(line_id),
+                    scenery_line.x0(),
+                    scenery_line.y0(),
+                    scenery_line.x1(),
+                    scenery_line.y1(),
+                ));
+            }
+        }
+        line_id += 1;
+    }
+
+    // The format only has a single well size for every line
+    let mut gravity_well_size = track.gravity_well_size();
+    if let Some(first_line) = track.standard_lines().first() {
+        let height = first_line.height();
+        for standard_line in track.standard_lines() {
+            #[expect(clippy::float_cmp)]
+            if standard_line.height() != height {
+                return Err(JsonWriteError::InconsistentLineHeight(format!(
+                    "{} != {}",
+                    standard_line.height(),
+                    height
+                )));
+            }
+        }
+        match gravity_well_size {
+            #[expect(clippy::float_cmp)]
+            Some(well_size) if well_size != height => {
+                return Err(JsonWriteError::InconsistentLineHeight(format!(
+                    "gravity well size {} != line height {}",
+                    well_size, height
+                )));
+            }
+            #[expect(clippy::float_cmp)]
+            None if height != 10.0 => gravity_well_size = Some(height),
+            _ => {}
+        }
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    let start_gravity = track.start_gravity().map(|gravity| {
+        let gravity = to_lra_gravity(gravity);
+        (gravity.x() as f32, gravity.y() as f32)
+    });
+
+    // Track keeps folders apart from layers, so folders are written first and layers find theirs by folder id
+    let mut layers = Vec::new();
+
+    for folder in track.layer_folders() {
+        let size = track
+            .layers()
+            .iter()
+            .filter(|layer| layer.folder_id() == Some(folder.id()))
+            .count();
+        layers.push(json_layer_folder(folder, u32::try_from(size)?));
+    }
+
+    for layer in track.layers() {
+        layers.push(json_layer(layer));
+    }
+
+    // A single LRA rider is written in the legacy format, which LRA and its forks also understand
+    let mut riders = None;
+    let mut start_pos = Some(V2::default());
+    let mut zero_start = None;
+
+    if let [rider] = track.riders().as_slice()
+        && rider.remount_version() == RemountVersion::LRA
+    {
+        if let Some(start_angle) = rider.start_angle() {
+            return Err(JsonWriteError::UnsupportedStartAngle(
+                start_angle.to_string(),
+            ));
+        }
+
+        let start_velocity = rider.start_velocity().unwrap_or(Vector2Df::new(0.4, 0.0));
+        if start_velocity == Vector2Df::zero() {
+            zero_start = Some(true);
+        } else if start_velocity != Vector2Df::new(0.4, 0.0) {
+            return Err(JsonWriteError::UnsupportedStartVelocity(format!(
+                "{:?}",
+                start_velocity
+            )));
+        } else {
+            zero_start = Some(false);
+        }
+
+        let start_offset = rider.start_offset().unwrap_or(Vector2Df::zero());
+        start_pos = Some(V2 {
+            x: start_offset.x(),
+            y: start_offset.y(),
+        });
+    } else {
+        let mut json_riders = Vec::new();
+
+        for rider in track.riders() {
+            let remountable = match rider.remount_version() {
+                RemountVersion::None => None,
+                RemountVersion::ComV1 => Some(FaultyBool::BoolRep(true)),
+                RemountVersion::ComV2 => Some(FaultyBool::IntRep(1)),
+                RemountVersion::LRA => {
+                    return Err(JsonWriteError::UnsupportedRemountVersion(
+                        rider.remount_version().to_string(),
+                    ));
+                }
+            };
+
+            let start_offset = rider.start_offset().unwrap_or(Vector2Df::zero());
+            let start_velocity = rider.start_velocity().unwrap_or(Vector2Df::new(0.4, 0.0));
+
+            json_riders.push(JsonRider {
+                start_pos: V2 {
+                    x: start_offset.x(),
+                    y: start_offset.y(),
+                },
+                start_vel: V2 {
+                    x: start_velocity.x(),
+                    y: start_velocity.y(),
+                },
+                angle: rider.start_angle(),
+                remountable,
+            });
+        }
+
+        riders = Some(json_riders);
+    }
+
+    // Unused trigger properties are set to -999
+    let mut time_based_triggers = Vec::new();
+
+    for trigger in track.triggers() {
+        let unused = Some(FaultyU32::Invalid(-999));
+        let mut json_trigger = LRAJsonTrigger {
+            trigger_type: 0,
+            start: trigger.start_frame(),
+            end: trigger.end_frame(),
+            zoom_target: -999.0,
+            background_red: unused,
+            background_green: unused,
+            background_blue: unused,
+            line_red: unused,
+            line_green: unused,
+            line_blue: unused,
+        };
+
+        match trigger.event() {
+            TriggerEvent::Zoom(target_zoom) => {
+                json_trigger.trigger_type = 0;
+                json_trigger.zoom_target = to_lra_zoom(target_zoom);
+            }
+            TriggerEvent::BackgroundColor(color) => {
+                json_trigger.trigger_type = 1;
+                json_trigger.background_red = Some(FaultyU32::Valid(u32::from(color.red())));
+                json_trigger.background_green = Some(FaultyU32::Valid(u32::from(color.green())));
+                json_trigger.background_blue = Some(FaultyU32::Valid(u32::from(color.blue())));
+            }
+            TriggerEvent::LineColor(color) => {
+                json_trigger.trigger_type = 2;
+                json_trigger.line_red = Some(FaultyU32::Valid(u32::from(color.red())));
+                json_trigger.line_green = Some(FaultyU32::Valid(u32::from(color.green())));
+                json_trigger.line_blue = Some(FaultyU32::Valid(u32::from(color.blue())));
+            }
+        }
+
+        time_based_triggers.push(json_trigger);
+    }
+
+    let json_track = JsonTrack {
+        label: track.title().clone(),
+        creator: track.artist().clone(),
+        description: track.description().clone(),
+        duration: track.duration(),
+        version: version.to_string(),
+        lines: Some(lines),
+        layers: (!layers.is_empty()).then_some(layers),
+        riders,
+        script: None,
+        start_pos,
+        line_array: (!line_array.is_empty()).then_some(line_array),
+        start_zoom: track.start_zoom().map(to_lra_zoom),
+        zero_start,
+        line_based_triggers: None,
+        time_based_triggers: (!time_based_triggers.is_empty()).then_some(time_based_triggers),
+        start_gravity_x: start_gravity.map(|gravity| gravity.0),
+        start_gravity_y: start_gravity.map(|gravity| gravity.1),
+        gravity_well_size,
+        start_bg_color_red: track
+            .start_background_color()
+            .map(|color| u32::from(color.red())),
+        start_bg_color_green: track
+            .start_background_color()
+            .map(|color| u32::from(color.green())),
+        start_bg_color_blue: track
+            .start_background_color()
+            .map(|color| u32::from(color.blue())),
+        start_line_color_red: track.start_line_color().map(|color| u32::from(color.red())),
+        start_line_color_green: track
+            .start_line_color()
+            .map(|color| u32::from(color.green())),
+        start_line_color_blue: track
+            .start_line_color()
+            .map(|color| u32::from(color.blue())),
+    };
+
+    Ok(serde_json::to_vec(&json_track)?)
+}
+
+fn json_layer(layer: &Layer) -> JsonLayer {
+    let name = layer.name().clone().unwrap_or_default();
+
+    // Layer colors are stored as a css hex prefix of the layer name
+    let name = match layer.color() {
+        Some(color) => format!("{}{}", color.to_css_string(), name),
+        None => name,
+    };
+
+    JsonLayer {
+        id: layer.id(),
+        layer_type: Some(0),
+        name,
+        visible: layer.visible().unwrap_or(true),
+        editable: layer.editable(),
+        folder_id: Some(match layer.folder_id() {
+            Some(folder_id) => FaultyU32::Valid(folder_id),
+            None => FaultyU32::Invalid(-1),
+        }),
+        size: None,
+    }
+}
+
+fn json_layer_folder(layer_folder: &LayerFolder, size: u32) -> JsonLayer {
+    JsonLayer {
+        id: layer_folder.id(),
+        layer_type: Some(1),
+        name: layer_folder.name().clone().unwrap_or_default(),
+        visible: layer_folder.visible().unwrap_or(true),
+        editable: layer_folder.editable(),
+        folder_id: None,
+        size: Some(size),
+    }
+}
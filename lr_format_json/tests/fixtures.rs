@@ -4,7 +4,7 @@ mod test {
     use geometry::{Line, Point};
     use lr_format_core::{
         GridVersion, LayerBuilder, LayerFolderBuilder, RemountVersion, RiderBuilder,
-        SceneryLineBuilder, StandardLineBuilder, TrackBuilder,
+        SceneryLineBuilder, StandardLineBuilder, TrackBuilder, TriggerBuilder, TriggerEvent,
     };
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -25,6 +25,7 @@ mod test {
         let mut rider = RiderBuilder::new(RemountVersion::None);
         rider.start_offset(Vector2Df::zero());
         rider.start_velocity(Vector2Df::new(0.4, 0.0));
+        rider.start_angle(0.0);
         expected.riders().push(rider);
 
         let mut layer = LayerBuilder::new(0);
@@ -75,6 +76,7 @@ mod test {
         let mut expected = TrackBuilder::new(GridVersion::V6_2);
 
         expected.title("test".to_string());
+        expected.start_zoom(2.0);
 
         let mut rider = RiderBuilder::new(RemountVersion::LRA);
         rider.start_offset(Vector2Df::zero());
@@ -111,6 +113,11 @@ mod test {
         let mut expected = TrackBuilder::new(GridVersion::V6_2);
 
         expected.title("test".to_string());
+        expected.start_zoom(2.0);
+        expected.start_gravity(Vector2Df::new(0.0, -0.175));
+        expected.gravity_well_size(5.0);
+        expected.start_background_color(RGBColor::new(255, 255, 255));
+        expected.start_line_color(RGBColor::new(0, 0, 0));
 
         let mut rider = RiderBuilder::new(RemountVersion::LRA);
         rider.start_offset(Vector2Df::zero());
@@ -138,6 +145,20 @@ mod test {
             SceneryLineBuilder::new(Line::new(Point::new(-33.0, -33.0), Point::new(-3.0, -32.0)));
         expected.scenery_lines().push(line);
 
+        expected
+            .triggers()
+            .push(TriggerBuilder::new(TriggerEvent::Zoom(2.0), 0, 40));
+        expected.triggers().push(TriggerBuilder::new(
+            TriggerEvent::BackgroundColor(RGBColor::new(255, 255, 255)),
+            1,
+            41,
+        ));
+        expected.triggers().push(TriggerBuilder::new(
+            TriggerEvent::LineColor(RGBColor::new(0, 0, 0)),
+            1,
+            41,
+        ));
+
         assert_eq!(result, expected.build());
     }
 
@@ -269,4 +290,124 @@ mod test {
 
         assert_eq!(result, expected.build());
     }
+
+    #[test]
+    fn roundtrip() {
+        for entry in fs::read_dir("../fixtures/lr_format_json").expect("Failed to read fixtures") {
+            let file_name = entry.expect("Failed to read fixture entry").path();
+            let file = fs::read(&file_name).expect("Failed to read JSON file");
+            let track = lr_format_json::read(&file).expect("Failed to parse track file");
+            let written = lr_format_json::write(&track).expect("Failed to write track file");
+            let result = lr_format_json::read(&written).expect("Failed to parse written file");
+            assert_eq!(result, track, "{}", file_name.display());
+        }
+    }
+
+    #[test]
+    fn conflicting_well_size() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        track.riders().push(RiderBuilder::new(RemountVersion::None));
+        let mut line = StandardLineBuilder::new(Line::new(Point::zero(), Point::new(1.0, 0.0)));
+        line.height(5.0);
+        track.standard_lines().push(line);
+        track.gravity_well_size(5.0);
+        let track = track.build();
+        lr_format_json::write(&track).expect("Failed to write track file");
+
+        let mut track = TrackBuilder::from(track);
+        track.gravity_well_size(20.0);
+        assert!(matches!(
+            lr_format_json::write(&track.build()),
+            Err(lr_format_json::JsonWriteError::InconsistentLineHeight(_))
+        ));
+    }
+
+    #[test]
+    fn scenery_width_after_legacy_line() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        track.riders().push(RiderBuilder::new(RemountVersion::None));
+        track
+            .scenery_lines()
+            .push(SceneryLineBuilder::new(Line::new(
+                Point::zero(),
+                Point::new(1.0, 0.0),
+            )));
+        let mut line = SceneryLineBuilder::new(Line::new(Point::zero(), Point::new(0.0, 1.0)));
+        line.width(2.0);
+        track.scenery_lines().push(line);
+        assert!(matches!(
+            lr_format_json::write(&track.build()),
+            Err(lr_format_json::JsonWriteError::InconsistentSceneryWidth(_))
+        ));
+    }
+
+    #[test]
+    fn legacy_rider_start_angle() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        let mut rider = RiderBuilder::new(RemountVersion::LRA);
+        rider.start_angle(45.0);
+        track.riders().push(rider);
+        assert!(matches!(
+            lr_format_json::write(&track.build()),
+            Err(lr_format_json::JsonWriteError::UnsupportedStartAngle(_))
+        ));
+    }
+
+    #[test]
+    fn layer_order() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        let mut rider = RiderBuilder::new(RemountVersion::None);
+        rider.start_offset(Vector2Df::zero());
+        rider.start_velocity(Vector2Df::new(0.4, 0.0));
+        track.riders().push(rider);
+
+        for (id, folder_id) in [(0, Some(5)), (1, None), (2, Some(5)), (4, Some(3))] {
+            let mut layer = LayerBuilder::new(id);
+            layer.name(String::new());
+            layer.visible(true);
+            if let Some(folder_id) = folder_id {
+                layer.folder_id(folder_id);
+            }
+            track.layers().push(layer);
+        }
+
+        for id in [5, 3, 6] {
+            let mut layer_folder = LayerFolderBuilder::new(id);
+            layer_folder.name(String::new());
+            layer_folder.visible(true);
+            track.layer_folders().push(layer_folder);
+        }
+
+        let track = track.build();
+        let written = lr_format_json::write(&track).expect("Failed to write track file");
+        let result = lr_format_json::read(&written).expect("Failed to parse written file");
+        assert_eq!(result, track);
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&written).expect("Failed to parse written JSON");
+        let sizes: Vec<_> = json
+            .get("layers")
+            .and_then(serde_json::Value::as_array)
+            .expect("Missing layers")
+            .iter()
+            .map(|layer| {
+                (
+                    layer.get("id").and_then(serde_json::Value::as_u64),
+                    layer.get("size").and_then(serde_json::Value::as_u64),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (Some(5), Some(2)),
+                (Some(3), Some(1)),
+                (Some(6), Some(0)),
+                (Some(0), None),
+                (Some(1), None),
+                (Some(2), None),
+                (Some(4), None),
+            ]
+        );
+    }
 }
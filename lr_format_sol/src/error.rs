@@ -69,3 +69,81 @@ impl From<num::TryFromIntError> for SolReadError {
         SolReadError::Other(Box::new(value))
     }
 }
+
+#[derive(Debug)]
+pub enum SolWriteError {
+    InvalidTrackList(String),
+    InvalidTrackIndex(String),
+    UnsupportedRiderCount(String),
+    UnsupportedRemountVersion(String),
+    UnsupportedStartVelocity(String),
+    UnsupportedLineMultiplier(String),
+    UnsupportedLineHeight(String),
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for SolWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::InvalidTrackList(e) => write!(f, "Invalid track list: {}", e),
+            Self::InvalidTrackIndex(e) => write!(f, "Invalid track index: {}", e),
+            Self::UnsupportedRiderCount(e) => write!(f, "Unsupported rider count: {}", e),
+            Self::UnsupportedRemountVersion(e) => write!(f, "Unsupported remount version: {}", e),
+            Self::UnsupportedStartVelocity(e) => write!(f, "Unsupported start velocity: {}", e),
+            Self::UnsupportedLineMultiplier(e) => write!(f, "Unsupported line multiplier: {}", e),
+            Self::UnsupportedLineHeight(e) => write!(f, "Unsupported line height: {}", e),
+            Self::Other(e) => write!(f, "Other error occurred: {}", e),
+        }
+    }
+}
+
+impl Error for SolWriteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            SolWriteError::Other(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SolReadError> for SolWriteError {
+    fn from(value: SolReadError) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<amf0::SerializationError> for SolWriteError {
+    fn from(value: amf0::SerializationError) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<amf0::DeserializationError> for SolWriteError {
+    fn from(value: amf0::DeserializationError) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<io::Error> for SolWriteError {
+    fn from(value: io::Error) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<str::Utf8Error> for SolWriteError {
+    fn from(value: str::Utf8Error) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<num::TryFromIntError> for SolWriteError {
+    fn from(value: num::TryFromIntError) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<num::ParseIntError> for SolWriteError {
+    fn from(value: num::ParseIntError) -> Self {
+        SolWriteError::Other(Box::new(value))
+    }
+}
@@ -2,6 +2,8 @@
 
 mod error;
 mod reader;
+mod writer;
 
-pub use error::SolReadError;
+pub use error::{SolReadError, SolWriteError};
 pub use reader::{get_track_count, read};
+pub use writer::{insert_track, remove_track, replace_track, write};
@@ -23,54 +23,7 @@ pub fn read(data: &[u8], track_index: Option<u32>) -> Result<Track, SolReadError
     let data_size = u64::try_from(data.len())?;
     let mut bytes = Cursor::new(data);
 
-    // Magic number
-    let mut magic_number = [0_u8; 2];
-    bytes.read_exact(&mut magic_number)?;
-
-    if magic_number != [0x00, 0xBF] {
-        return Err(SolReadError::InvalidMagicNumber(format!(
-            "{:02X?}",
-            &magic_number,
-        )));
-    }
-
-    // Header
-    let _file_size = bytes.read_u32_be()? + 6;
-
-    let mut tag = [0_u8; 4];
-    bytes.read_exact(&mut tag)?;
-
-    if tag != [b'T', b'C', b'S', b'O'] {
-        return Err(SolReadError::InvalidMagicNumber(format!("{:02X?}", &tag)));
-    }
-
-    let mut marker = [0_u8; 6];
-    bytes.read_exact(&mut marker)?;
-    if marker != [0x00, 0x04, 0x00, 0x00, 0x00, 0x00] {
-        return Err(SolReadError::InvalidMagicNumber(format!(
-            "{:02X?}",
-            &marker,
-        )));
-    }
-
-    let sol_string_length = bytes.read_u16_be()?;
-    let mut sol_name = vec![0; usize::from(sol_string_length)];
-    bytes.read_exact(&mut sol_name)?;
-    if str::from_utf8(&sol_name)? != "savedLines" {
-        return Err(SolReadError::InvalidMagicNumber(format!(
-            "{:02X?}",
-            &sol_name,
-        )));
-    }
-
-    let _padding = bytes.read_u32_be()?;
-
-    let data_string_length = bytes.read_u16_be()?;
-    let mut data_name = vec![0; usize::from(data_string_length)];
-    bytes.read_exact(&mut data_name)?;
-    if str::from_utf8(&data_name)? != "trackList" {
-        return Err(SolReadError::MissingTrackList);
-    }
+    read_header(&mut bytes)?;
 
     // Track Data
     let current_pos = bytes.position();
@@ -194,6 +147,7 @@ pub fn read(data: &[u8], track_index: Option<u32>) -> Result<Track, SolReadError
             .ok_or(SolReadError::InvalidLinesList(format!("{:?}", val)))?;
 
         let mut ordered_standard_lines = Vec::new();
+        let mut ordered_scenery_lines = Vec::new();
 
         #[expect(clippy::iter_over_hash_type)]
         for line_amf in lines_list.values() {
@@ -300,16 +254,75 @@ pub fn read(data: &[u8], track_index: Option<u32>) -> Result<Track, SolReadError
                 ordered_standard_lines.push((id, standard_line));
             } else {
                 let scenery_line = SceneryLineBuilder::new(endpoints);
-                track.scenery_lines().push(scenery_line);
+                ordered_scenery_lines.push((id, scenery_line));
             }
         }
 
         ordered_standard_lines.sort_by_key(|t| t.0);
+        ordered_scenery_lines.sort_by_key(|t| t.0);
 
         for (_id, line) in ordered_standard_lines {
             track.standard_lines().push(line);
         }
+
+        for (_id, line) in ordered_scenery_lines {
+            track.scenery_lines().push(line);
+        }
     }
 
     Ok(track.build())
 }
+
+// Validates the shared object header, leaving the cursor positioned at the start of the track list value
+pub(crate) fn read_header(bytes: &mut Cursor<&[u8]>) -> Result<(), SolReadError> {
+    // Magic number
+    let mut magic_number = [0_u8; 2];
+    bytes.read_exact(&mut magic_number)?;
+
+    if magic_number != [0x00, 0xBF] {
+        return Err(SolReadError::InvalidMagicNumber(format!(
+            "{:02X?}",
+            &magic_number,
+        )));
+    }
+
+    // Header
+    let _file_size = bytes.read_u32_be()? + 6;
+
+    let mut tag = [0_u8; 4];
+    bytes.read_exact(&mut tag)?;
+
+    if tag != [b'T', b'C', b'S', b'O'] {
+        return Err(SolReadError::InvalidMagicNumber(format!("{:02X?}", &tag)));
+    }
+
+    let mut marker = [0_u8; 6];
+    bytes.read_exact(&mut marker)?;
+    if marker != [0x00, 0x04, 0x00, 0x00, 0x00, 0x00] {
+        return Err(SolReadError::InvalidMagicNumber(format!(
+            "{:02X?}",
+            &marker,
+        )));
+    }
+
+    let sol_string_length = bytes.read_u16_be()?;
+    let mut sol_name = vec![0; usize::from(sol_string_length)];
+    bytes.read_exact(&mut sol_name)?;
+    if str::from_utf8(&sol_name)? != "savedLines" {
+        return Err(SolReadError::InvalidMagicNumber(format!(
+            "{:02X?}",
+            &sol_name,
+        )));
+    }
+
+    let _padding = bytes.read_u32_be()?;
+
+    let data_string_length = bytes.read_u16_be()?;
+    let mut data_name = vec![0; usize::from(data_string_length)];
+    bytes.read_exact(&mut data_name)?;
+    if str::from_utf8(&data_name)? != "trackList" {
+        return Err(SolReadError::MissingTrackList);
+    }
+
+    Ok(())
+}
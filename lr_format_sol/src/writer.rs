@@ -0,0 +1,331 @@
+use crate::{SolWriteError, reader::read_header};
+use amf0::{Amf0Value, deserialize_value, serialize};
+use lr_format_core::{GridVersion, RemountVersion, Track};
+use quick_byte::{QuickRead as _, QuickWrite as _};
+use std::io::{Cursor, Read as _, Write as _};
+use vector2d::Vector2Df;
+
+const OBJECT_MARKER: u8 = 3;
+const ECMA_ARRAY_MARKER: u8 = 8;
+const OBJECT_END_MARKER: u8 = 9;
+
+/// Create an sol file containing only the given track
+pub fn write(track: &Track) -> Result<Vec<u8>, SolWriteError> {
+    write_track_entries(&[serialize_track(track)?])
+}
+
+/// Insert a track into an existing sol file, shifting the tracks at and after `track_index` up by one
+pub fn insert_track(
+    data: &[u8],
+    track_index: u32,
+    track: &Track,
+) -> Result<Vec<u8>, SolWriteError> {
+    let mut entries = read_track_entries(data)?;
+    let index = usize::try_from(track_index)?;
+
+    if index > entries.len() {
+        return Err(SolWriteError::InvalidTrackIndex(track_index.to_string()));
+    }
+
+    entries.insert(index, serialize_track(track)?);
+    write_track_entries(&entries)
+}
+
+/// Replace the track at `track_index` of an existing sol file, leaving the other tracks untouched
+pub fn replace_track(
+    data: &[u8],
+    track_index: u32,
+    track: &Track,
+) -> Result<Vec<u8>, SolWriteError> {
+    let mut entries = read_track_entries(data)?;
+    let entry = entries
+        .get_mut(usize::try_from(track_index)?)
+        .ok_or(SolWriteError::InvalidTrackIndex(track_index.to_string()))?;
+
+    *entry = serialize_track(track)?;
+    write_track_entries(&entries)
+}
+
+/// Remove the track at `track_index` of an existing sol file, shifting the tracks after it down by one
+pub fn remove_track(data: &[u8], track_index: u32) -> Result<Vec<u8>, SolWriteError> {
+    let mut entries = read_track_entries(data)?;
+    let index = usize::try_from(track_index)?;
+
+    if index >= entries.len() {
+        return Err(SolWriteError::InvalidTrackIndex(track_index.to_string()));
+    }
+
+    entries.remove(index);
+    write_track_entries(&entries)
+}
+
+// Splits the track list into the raw bytes of each track value, ordered by track index
+fn read_track_entries(data: &[u8]) -> Result<Vec<Vec<u8>>, SolWriteError> {
+    let mut bytes = Cursor::new(data);
+
+    read_header(&mut bytes)?;
+
+    match bytes.read_u8()? {
+        ECMA_ARRAY_MARKER => {
+            let _associative_count = bytes.read_u32_be()?;
+        }
+        OBJECT_MARKER => {}
+        other => return Err(SolWriteError::InvalidTrackList(format!("{:02X?}", other))),
+    }
+
+    let mut entries = Vec::new();
+
+    loop {
+        let label_length = bytes.read_u16_be()?;
+
+        if label_length == 0 {
+            let end_marker = bytes.read_u8()?;
+            if end_marker != OBJECT_END_MARKER {
+                return Err(SolWriteError::InvalidTrackList(format!(
+                    "{:02X?}",
+                    end_marker
+                )));
+            }
+            break;
+        }
+
+        let mut label = vec![0; usize::from(label_length)];
+        bytes.read_exact(&mut label)?;
+        let track_index = str::from_utf8(&label)?.parse::<u32>()?;
+
+        let start = usize::try_from(bytes.position())?;
+        deserialize_value(&mut bytes)?;
+        let end = usize::try_from(bytes.position())?;
+
+        let value = data
+            .get(start..end)
+            .ok_or(SolWriteError::InvalidTrackList(format!(
+                "{}..{}",
+                start, end
+            )))?;
+        entries.push((track_index, value.to_vec()));
+    }
+
+    entries.sort_by_key(|t| t.0);
+
+    Ok(entries.into_iter().map(|(_index, value)| value).collect())
+}
+
+fn write_track_entries(entries: &[Vec<u8>]) -> Result<Vec<u8>, SolWriteError> {
+    let mut body = Vec::new();
+
+    // Header
+    body.write_all(b"TCSO")?;
+    body.write_all(&[0x00, 0x04, 0x00, 0x00, 0x00, 0x00])?;
+    write_property_name(&mut body, "savedLines")?;
+    body.write_u32_be(0)?;
+    write_property_name(&mut body, "trackList")?;
+
+    // Track Data
+    body.write_u8(ECMA_ARRAY_MARKER)?;
+    body.write_u32_be(u32::try_from(entries.len())?)?;
+
+    for (track_index, entry) in entries.iter().enumerate() {
+        write_property_name(&mut body, &track_index.to_string())?;
+        body.write_all(entry)?;
+    }
+
+    write_object_end(&mut body)?;
+    body.write_u8(0)?;
+
+    let mut bytes = Vec::new();
+
+    // Magic number
+    bytes.write_all(&[0x00, 0xBF])?;
+    bytes.write_u32_be(u32::try_from(body.len())?)?;
+    bytes.write_all(&body)?;
+
+    Ok(bytes)
+}
+
+fn serialize_track(track: &Track) -> Result<Vec<u8>, SolWriteError> {
+    if track.riders().len() > 1 {
+        return Err(SolWriteError::UnsupportedRiderCount(
+            track.riders().len().to_string(),
+        ));
+    }
+
+    let rider = track.riders().first();
+    let start_position = rider
+        .and_then(|rider| rider.start_offset())
+        .unwrap_or(Vector2Df::zero());
+    let start_velocity = rider
+        .and_then(|rider| rider.start_velocity())
+        .unwrap_or(Vector2Df::new(0.4, 0.0));
+    let remount_version = rider.map_or(RemountVersion::None, |rider| rider.remount_version());
+
+    if remount_version != RemountVersion::None {
+        return Err(SolWriteError::UnsupportedRemountVersion(
+            remount_version.to_string(),
+        ));
+    }
+
+    let zero_start = start_velocity == Vector2Df::zero();
+
+    if !zero_start && start_velocity != Vector2Df::new(0.4, 0.0) {
+        return Err(SolWriteError::UnsupportedStartVelocity(format!(
+            "{:?}",
+            start_velocity
+        )));
+    }
+
+    let mut bytes = Vec::new();
+    bytes.write_u8(OBJECT_MARKER)?;
+
+    match track.grid_version() {
+        GridVersion::V6_0 => {}
+        GridVersion::V6_1 => {
+            write_property(&mut bytes, "version", Amf0Value::Utf8String("6.1".into()))?;
+        }
+        GridVersion::V6_2 => {
+            write_property(&mut bytes, "version", Amf0Value::Utf8String("6.2".into()))?;
+        }
+    }
+
+    write_property_name(&mut bytes, "startLine")?;
+    bytes.write_u8(ECMA_ARRAY_MARKER)?;
+    bytes.write_u32_be(2)?;
+    write_property(&mut bytes, "0", Amf0Value::Number(start_position.x()))?;
+    write_property(&mut bytes, "1", Amf0Value::Number(start_position.y()))?;
+    write_object_end(&mut bytes)?;
+
+    let line_count = track.standard_lines().len() + track.scenery_lines().len();
+    let line_count = u32::try_from(line_count)?;
+    write_property(
+        &mut bytes,
+        "level",
+        Amf0Value::Number(f64::from(line_count)),
+    )?;
+
+    write_property_name(&mut bytes, "data")?;
+    bytes.write_u8(ECMA_ARRAY_MARKER)?;
+    bytes.write_u32_be(line_count)?;
+
+    let mut line_id: u32 = 0;
+
+    for line in track.standard_lines() {
+        let line_type = match line.multiplier() {
+            0.0 => 0,
+            1.0 => 1,
+            other => return Err(SolWriteError::UnsupportedLineMultiplier(other.to_string())),
+        };
+
+        #[expect(clippy::float_cmp)]
+        if line.height() != 10.0 {
+            return Err(SolWriteError::UnsupportedLineHeight(
+                line.height().to_string(),
+            ));
+        }
+
+        let extension = u8::from(line.left_extension()) | (u8::from(line.right_extension()) << 1);
+
+        write_line(
+            &mut bytes,
+            line_id,
+            [line.x0(), line.y0(), line.x1(), line.y1()],
+            extension,
+            line.flipped(),
+            line_type,
+        )?;
+        line_id += 1;
+    }
+
+    for line in track.scenery_lines() {
+        write_line(
+            &mut bytes,
+            line_id,
+            [line.x0(), line.y0(), line.x1(), line.y1()],
+            0,
+            false,
+            2,
+        )?;
+        line_id += 1;
+    }
+
+    write_object_end(&mut bytes)?;
+
+    // Later flash builds only check for the presence of trackData to start the rider with zero velocity
+    if zero_start {
+        write_property_name(&mut bytes, "trackData")?;
+        bytes.write_u8(ECMA_ARRAY_MARKER)?;
+        bytes.write_u32_be(3)?;
+        write_property(&mut bytes, "0", Amf0Value::Null)?;
+
+        write_property_name(&mut bytes, "1")?;
+        bytes.write_u8(ECMA_ARRAY_MARKER)?;
+        bytes.write_u32_be(3)?;
+        for index in 0..3 {
+            write_property(&mut bytes, &index.to_string(), Amf0Value::Null)?;
+        }
+        write_object_end(&mut bytes)?;
+
+        write_property_name(&mut bytes, "2")?;
+        bytes.write_u8(ECMA_ARRAY_MARKER)?;
+        bytes.write_u32_be(6)?;
+        for index in 0..5 {
+            write_property(&mut bytes, &index.to_string(), Amf0Value::Null)?;
+        }
+        write_property(&mut bytes, "5", Amf0Value::Boolean(true))?;
+        write_object_end(&mut bytes)?;
+
+        write_object_end(&mut bytes)?;
+    }
+
+    let label = track.title().clone().unwrap_or_default();
+    write_property(&mut bytes, "label", Amf0Value::Utf8String(label))?;
+
+    write_object_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+// Lines are ten element arrays of [x1, y1, x2, y2, extension, flipped, _, _, id, type]
+fn write_line(
+    bytes: &mut Vec<u8>,
+    line_id: u32,
+    endpoints: [f64; 4],
+    extension: u8,
+    flipped: bool,
+    line_type: u8,
+) -> Result<(), SolWriteError> {
+    write_property_name(bytes, &line_id.to_string())?;
+    bytes.write_u8(ECMA_ARRAY_MARKER)?;
+    bytes.write_u32_be(10)?;
+
+    for (index, value) in endpoints.into_iter().enumerate() {
+        write_property(bytes, &index.to_string(), Amf0Value::Number(value))?;
+    }
+
+    write_property(bytes, "4", Amf0Value::Number(f64::from(extension)))?;
+    write_property(bytes, "5", Amf0Value::Boolean(flipped))?;
+    write_property(bytes, "6", Amf0Value::Undefined)?;
+    write_property(bytes, "7", Amf0Value::Undefined)?;
+    write_property(bytes, "8", Amf0Value::Number(f64::from(line_id)))?;
+    write_property(bytes, "9", Amf0Value::Number(f64::from(line_type)))?;
+    write_object_end(bytes)?;
+
+    Ok(())
+}
+
+fn write_property(bytes: &mut Vec<u8>, name: &str, value: Amf0Value) -> Result<(), SolWriteError> {
+    write_property_name(bytes, name)?;
+    bytes.write_all(&serialize(&vec![value])?)?;
+    Ok(())
+}
+
+fn write_property_name(bytes: &mut Vec<u8>, name: &str) -> Result<(), SolWriteError> {
+    bytes.write_u16_be(u16::try_from(name.len())?)?;
+    bytes.write_all(name.as_bytes())?;
+    Ok(())
+}
+
+fn write_object_end(bytes: &mut Vec<u8>) -> Result<(), SolWriteError> {
+    bytes.write_u16_be(0)?;
+    bytes.write_u8(OBJECT_END_MARKER)?;
+    Ok(())
+}
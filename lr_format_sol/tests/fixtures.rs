@@ -168,4 +168,78 @@ mod test {
 
         assert_eq!(result, expected.build());
     }
+
+    #[test]
+    fn roundtrip() {
+        for entry in fs::read_dir("../fixtures/lr_format_sol").expect("Failed to read fixtures") {
+            let file_name = entry.expect("Failed to read fixture entry").path();
+            let file = fs::read(&file_name).expect("Failed to read SOL file");
+
+            for track_index in 0..lr_format_sol::get_track_count(&file) {
+                let track = lr_format_sol::read(&file, Some(track_index))
+                    .expect("Failed to parse track file");
+                let written = lr_format_sol::write(&track).expect("Failed to write track file");
+                let result =
+                    lr_format_sol::read(&written, None).expect("Failed to parse written file");
+                assert_eq!(result, track, "{}", file_name.display());
+                assert_eq!(lr_format_sol::get_track_count(&written), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn insert_and_remove_track() {
+        let file_name = "../fixtures/lr_format_sol/multi_track_61.sol";
+        let file = fs::read(file_name).expect("Failed to read SOL file");
+        let other_file = fs::read("../fixtures/lr_format_sol/all_features.sol")
+            .expect("Failed to read SOL file");
+        let track = lr_format_sol::read(&other_file, None).expect("Failed to parse track file");
+
+        let inserted =
+            lr_format_sol::insert_track(&file, 1, &track).expect("Failed to insert track");
+        assert_eq!(lr_format_sol::get_track_count(&inserted), 3);
+
+        let result = lr_format_sol::read(&inserted, Some(1)).expect("Failed to parse track file");
+        assert_eq!(result, track);
+
+        for (old_index, new_index) in [(0, 0), (1, 2)] {
+            let result =
+                lr_format_sol::read(&inserted, Some(new_index)).expect("Failed to parse track");
+            let expected =
+                lr_format_sol::read(&file, Some(old_index)).expect("Failed to parse track");
+            assert_eq!(result, expected);
+        }
+
+        let result = lr_format_sol::insert_track(&file, 3, &track);
+        assert!(
+            result.is_err(),
+            "Insertion should not leave gaps between tracks"
+        );
+
+        let removed = lr_format_sol::remove_track(&inserted, 1).expect("Failed to remove track");
+        assert_eq!(removed, file, "Other tracks should be kept byte-identical");
+    }
+
+    #[test]
+    fn replace_track() {
+        let file_name = "../fixtures/lr_format_sol/multi_track_61.sol";
+        let file = fs::read(file_name).expect("Failed to read SOL file");
+        let other_file = fs::read("../fixtures/lr_format_sol/zero_start_line_props.sol")
+            .expect("Failed to read SOL file");
+        let track = lr_format_sol::read(&other_file, None).expect("Failed to parse track file");
+        let original = lr_format_sol::read(&file, Some(1)).expect("Failed to parse track file");
+
+        let replaced =
+            lr_format_sol::replace_track(&file, 0, &track).expect("Failed to replace track");
+        assert_eq!(lr_format_sol::get_track_count(&replaced), 2);
+
+        let result = lr_format_sol::read(&replaced, Some(0)).expect("Failed to parse track file");
+        assert_eq!(result, track);
+
+        let result = lr_format_sol::read(&replaced, Some(1)).expect("Failed to parse track file");
+        assert_eq!(result, original);
+
+        let result = lr_format_sol::replace_track(&file, 2, &track);
+        assert!(result.is_err(), "There should only be two tracks present");
+    }
 }
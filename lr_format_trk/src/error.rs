@@ -67,3 +67,49 @@ impl From<str::Utf8Error> for TrkReadError {
         TrkReadError::Other(Box::new(value))
     }
 }
+
+#[derive(Debug)]
+pub enum TrkWriteError {
+    UnsupportedGridVersion(String),
+    UnsupportedRiderCount(String),
+    UnsupportedRemountVersion(String),
+    UnsupportedStartVelocity(String),
+    UnsupportedLineMultiplier(String),
+    InconsistentLineHeight(String),
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for TrkWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::UnsupportedGridVersion(e) => write!(f, "Unsupported grid version: {}", e),
+            Self::UnsupportedRiderCount(e) => write!(f, "Unsupported rider count: {}", e),
+            Self::UnsupportedRemountVersion(e) => write!(f, "Unsupported remount version: {}", e),
+            Self::UnsupportedStartVelocity(e) => write!(f, "Unsupported start velocity: {}", e),
+            Self::UnsupportedLineMultiplier(e) => write!(f, "Unsupported line multiplier: {}", e),
+            Self::InconsistentLineHeight(e) => write!(f, "Inconsistent line height: {}", e),
+            Self::Other(e) => write!(f, "Other error occurred: {}", e),
+        }
+    }
+}
+
+impl Error for TrkWriteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            TrkWriteError::Other(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TrkWriteError {
+    fn from(value: io::Error) -> Self {
+        TrkWriteError::Other(Box::new(value))
+    }
+}
+
+impl From<num::TryFromIntError> for TrkWriteError {
+    fn from(value: num::TryFromIntError) -> Self {
+        TrkWriteError::Other(Box::new(value))
+    }
+}
@@ -1,9 +1,16 @@
-use crate::TrkReadError;
+use crate::{
+    FEATURE_6_1, FEATURE_BACKGROUND_COLOR_B, FEATURE_BACKGROUND_COLOR_G,
+    FEATURE_BACKGROUND_COLOR_R, FEATURE_FRICTIONLESS, FEATURE_GRAVITY_WELL_SIZE,
+    FEATURE_IGNORABLE_TRIGGER, FEATURE_LINE_COLOR_B, FEATURE_LINE_COLOR_G, FEATURE_LINE_COLOR_R,
+    FEATURE_RED_MULTIPLIER, FEATURE_REMOUNT, FEATURE_SCENERY_WIDTH, FEATURE_SONG_INFO,
+    FEATURE_START_ZOOM, FEATURE_TRIGGERS, FEATURE_X_GRAVITY, FEATURE_Y_GRAVITY, FEATURE_ZERO_START,
+    LineType, TrkReadError,
+};
 use color::RGBColor;
 use geometry::{Line, Point};
 use lr_format_core::{
     GridVersion, RemountVersion, RiderBuilder, SceneryLineBuilder, StandardLineBuilder, Track,
-    TrackBuilder,
+    TrackBuilder, TriggerBuilder, TriggerEvent,
     unit_conversion::{
         from_lra_audio_offset, from_lra_gravity, from_lra_scenery_width, from_lra_zoom,
     },
@@ -15,34 +22,6 @@ use std::{
 };
 use vector2d::Vector2Df;
 
-// These string literals are implementation-specific, do not modify
-const FEATURE_RED_MULTIPLIER: &str = "REDMULTIPLIER";
-const FEATURE_SCENERY_WIDTH: &str = "SCENERYWIDTH";
-const FEATURE_SONG_INFO: &str = "SONGINFO";
-const FEATURE_IGNORABLE_TRIGGER: &str = "IGNORABLE_TRIGGER";
-const FEATURE_6_1: &str = "6.1";
-const FEATURE_ZERO_START: &str = "ZEROSTART";
-const FEATURE_REMOUNT: &str = "REMOUNT";
-const _FEATURE_FRICTIONLESS: &str = "FRICTIONLESS";
-const FEATURE_START_ZOOM: &str = "STARTZOOM";
-const FEATURE_X_GRAVITY: &str = "XGRAVITY";
-const FEATURE_Y_GRAVITY: &str = "YGRAVITY";
-const FEATURE_GRAVITY_WELL_SIZE: &str = "GRAVITYWELLSIZE";
-const FEATURE_BACKGROUND_COLOR_R: &str = "BGCOLORR";
-const FEATURE_BACKGROUND_COLOR_G: &str = "BGCOLORG";
-const FEATURE_BACKGROUND_COLOR_B: &str = "BGCOLORB";
-const FEATURE_LINE_COLOR_R: &str = "LINECOLORR";
-const FEATURE_LINE_COLOR_G: &str = "LINECOLORG";
-const FEATURE_LINE_COLOR_B: &str = "LINECOLORB";
-const FEATURE_TRIGGERS: &str = "TRIGGERS";
-
-#[derive(PartialEq)]
-enum LineType {
-    Standard,
-    Acceleration,
-    Scenery,
-}
-
 pub fn read(data: &Vec<u8>) -> Result<Track, TrkReadError> {
     let mut track = TrackBuilder::new(GridVersion::V6_2);
     let mut cursor = Cursor::new(data);
@@ -82,6 +61,7 @@ pub fn read(data: &Vec<u8>) -> Result<Track, TrkReadError> {
     };
 
     track.grid_version(grid_version);
+    track.frictionless(included_features.contains(FEATURE_FRICTIONLESS));
 
     if included_features.contains(FEATURE_SONG_INFO) {
         let mut song_string_length = 0;
@@ -322,90 +302,105 @@ pub fn read(data: &Vec<u8>) -> Result<Track, TrkReadError> {
                     match *values.get(0).ok_or(TrkReadError::EmptyTriggerData)? {
                         "0" => {
                             // Zoom
-                            let _target_zoom = from_lra_zoom(
+                            let target_zoom = from_lra_zoom(
                                 values
                                     .get(1)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<f32>()?,
                             );
-                            let _start_frame = u32::try_from(
+                            let start_frame = u32::try_from(
                                 values
                                     .get(2)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _end_frame = u32::try_from(
+                            let end_frame = u32::try_from(
                                 values
                                     .get(3)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
+                            track.triggers().push(TriggerBuilder::new(
+                                TriggerEvent::Zoom(target_zoom),
+                                start_frame,
+                                end_frame,
+                            ));
                         }
                         "1" => {
                             // Background Color
-                            let _red = u8::try_from(
+                            let red = u8::try_from(
                                 values
                                     .get(1)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _green = u8::try_from(
+                            let green = u8::try_from(
                                 values
                                     .get(2)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _blue = u8::try_from(
+                            let blue = u8::try_from(
                                 values
                                     .get(3)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _start_frame = u32::try_from(
+                            let start_frame = u32::try_from(
                                 values
                                     .get(4)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _end_frame = u32::try_from(
+                            let end_frame = u32::try_from(
                                 values
                                     .get(5)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
+                            track.triggers().push(TriggerBuilder::new(
+                                TriggerEvent::BackgroundColor(RGBColor::new(red, green, blue)),
+                                start_frame,
+                                end_frame,
+                            ));
                         }
                         "2" => {
                             // Line Color
-                            let _red = u8::try_from(
+                            let red = u8::try_from(
                                 values
                                     .get(1)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _green = u8::try_from(
+                            let green = u8::try_from(
                                 values
                                     .get(2)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _blue = u8::try_from(
+                            let blue = u8::try_from(
                                 values
                                     .get(3)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _start_frame = u32::try_from(
+                            let start_frame = u32::try_from(
                                 values
                                     .get(4)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
-                            let _end_frame = u32::try_from(
+                            let end_frame = u32::try_from(
                                 values
                                     .get(5)
                                     .ok_or(TrkReadError::InvalidTriggerData(trigger.to_string()))?
                                     .parse::<i32>()?,
                             )?;
+                            track.triggers().push(TriggerBuilder::new(
+                                TriggerEvent::LineColor(RGBColor::new(red, green, blue)),
+                                start_frame,
+                                end_frame,
+                            ));
                         }
                         other => {
                             return Err(TrkReadError::UnsupportedTriggerType(other.to_string()));
@@ -417,26 +412,44 @@ pub fn read(data: &Vec<u8>) -> Result<Track, TrkReadError> {
         }
     }
 
+    if let Some(start_zoom) = start_zoom {
+        track.start_zoom(start_zoom);
+    }
+
     // Default values assigned because LRA:CE and LRO don't write on absent features (eg gravity Y gets written when gravity X may not be)
 
-    let _start_zoom = start_zoom.unwrap_or(from_lra_zoom(4.0));
+    if start_gravity_x.is_some() || start_gravity_y.is_some() {
+        track.start_gravity(from_lra_gravity(Vector2Df::new(
+            start_gravity_x.unwrap_or(0.0),
+            start_gravity_y.unwrap_or(1.0),
+        )));
+    }
 
-    let _start_gravity = from_lra_gravity(Vector2Df::new(
-        start_gravity_x.unwrap_or(0.0),
-        start_gravity_y.unwrap_or(1.0),
-    ));
+    if start_bg_color_red.is_some()
+        || start_bg_color_green.is_some()
+        || start_bg_color_blue.is_some()
+    {
+        track.start_background_color(RGBColor::new(
+            start_bg_color_red.unwrap_or(244),
+            start_bg_color_green.unwrap_or(245),
+            start_bg_color_blue.unwrap_or(249),
+        ));
+    }
 
-    let _start_background_color = RGBColor::new(
-        start_bg_color_red.unwrap_or(244),
-        start_bg_color_green.unwrap_or(245),
-        start_bg_color_blue.unwrap_or(249),
-    );
+    if start_line_color_red.is_some()
+        || start_line_color_green.is_some()
+        || start_line_color_blue.is_some()
+    {
+        track.start_line_color(RGBColor::new(
+            start_line_color_red.unwrap_or(0),
+            start_line_color_green.unwrap_or(0),
+            start_line_color_blue.unwrap_or(0),
+        ));
+    }
 
-    let _start_line_color = RGBColor::new(
-        start_line_color_red.unwrap_or(0),
-        start_line_color_green.unwrap_or(0),
-        start_line_color_blue.unwrap_or(0),
-    );
+    if let Some(gravity_well_size) = gravity_well_size {
+        track.gravity_well_size(gravity_well_size);
+    }
 
     for line in track.standard_lines() {
         line.height(gravity_well_size.unwrap_or(10.0));
@@ -0,0 +1,326 @@
+use crate::{
+    FEATURE_6_1, FEATURE_BACKGROUND_COLOR_B, FEATURE_BACKGROUND_COLOR_G,
+    FEATURE_BACKGROUND_COLOR_R, FEATURE_FRICTIONLESS, FEATURE_GRAVITY_WELL_SIZE,
+    FEATURE_LINE_COLOR_B, FEATURE_LINE_COLOR_G, FEATURE_LINE_COLOR_R, FEATURE_RED_MULTIPLIER,
+    FEATURE_REMOUNT, FEATURE_SCENERY_WIDTH, FEATURE_SONG_INFO, FEATURE_START_ZOOM,
+    FEATURE_TRIGGERS, FEATURE_X_GRAVITY, FEATURE_Y_GRAVITY, FEATURE_ZERO_START, LineType,
+    TrkWriteError,
+};
+use geometry::Point;
+use lr_format_core::{
+    GridVersion, RemountVersion, Track, TriggerEvent,
+    unit_conversion::{to_lra_audio_offset, to_lra_gravity, to_lra_scenery_width, to_lra_zoom},
+};
+use quick_byte::QuickWrite as _;
+use std::{collections::HashMap, io::Write as _};
+use vector2d::Vector2Df;
+
+pub fn write(track: &Track) -> Result<Vec<u8>, TrkWriteError> {
+    let mut bytes = Vec::new();
+
+    if track.riders().len() > 1 {
+        return Err(TrkWriteError::UnsupportedRiderCount(
+            track.riders().len().to_string(),
+        ));
+    }
+
+    let rider = track.riders().first();
+    let start_position = rider
+        .and_then(|rider| rider.start_offset())
+        .unwrap_or(Vector2Df::zero());
+    let start_velocity = rider
+        .and_then(|rider| rider.start_velocity())
+        .unwrap_or(Vector2Df::new(0.4, 0.0));
+    let remount_version = rider.map_or(RemountVersion::None, |rider| rider.remount_version());
+
+    // Only include the features that are needed to represent the track, in the same order LRA writes them
+    let mut features = Vec::new();
+
+    #[expect(clippy::float_cmp)]
+    if track
+        .standard_lines()
+        .iter()
+        .any(|line| line.multiplier() != 0.0 && line.multiplier() != 1.0)
+    {
+        features.push(FEATURE_RED_MULTIPLIER);
+    }
+
+    #[expect(clippy::float_cmp)]
+    if track
+        .scenery_lines()
+        .iter()
+        .any(|line| line.width().is_some_and(|width| width != 1.0))
+    {
+        features.push(FEATURE_SCENERY_WIDTH);
+    }
+
+    if track.audio_filename().is_some() {
+        features.push(FEATURE_SONG_INFO);
+    }
+
+    match track.grid_version() {
+        GridVersion::V6_0 => {
+            return Err(TrkWriteError::UnsupportedGridVersion(
+                track.grid_version().to_string(),
+            ));
+        }
+        GridVersion::V6_1 => features.push(FEATURE_6_1),
+        GridVersion::V6_2 => {}
+    }
+
+    if start_velocity != Vector2Df::new(0.4, 0.0) {
+        if start_velocity != Vector2Df::zero() {
+            return Err(TrkWriteError::UnsupportedStartVelocity(format!(
+                "{:?}",
+                start_velocity
+            )));
+        }
+        features.push(FEATURE_ZERO_START);
+    }
+
+    match remount_version {
+        RemountVersion::None => {}
+        RemountVersion::LRA => features.push(FEATURE_REMOUNT),
+        RemountVersion::ComV1 | RemountVersion::ComV2 => {
+            return Err(TrkWriteError::UnsupportedRemountVersion(
+                remount_version.to_string(),
+            ));
+        }
+    }
+
+    if track.frictionless() {
+        features.push(FEATURE_FRICTIONLESS);
+    }
+
+    // Magic number
+    bytes.write_all(&[b'T', b'R', b'K', 0xF2])?;
+
+    // Version
+    bytes.write_u8(1)?;
+
+    let feature_string: String = features
+        .iter()
+        .flat_map(|feature| [*feature, ";"])
+        .collect();
+    bytes.write_u16_le(u16::try_from(feature_string.len())?)?;
+    bytes.write_all(feature_string.as_bytes())?;
+
+    if let Some(audio_filename) = track.audio_filename() {
+        let seconds_offset = to_lra_audio_offset(track.audio_offset_until_start().unwrap_or(0.0));
+        let song_string = format!("{}\r\n{}", audio_filename, seconds_offset);
+        let mut song_string_length = song_string.len();
+
+        loop {
+            // Write 7BitEncodedInt song string length
+            let byte = u8::try_from(song_string_length & 0x7F)?;
+            song_string_length >>= 7;
+
+            if song_string_length == 0 {
+                bytes.write_u8(byte)?;
+                break;
+            }
+
+            bytes.write_u8(byte | 0x80)?;
+        }
+
+        bytes.write_all(song_string.as_bytes())?;
+    }
+
+    bytes.write_f64_le(start_position.x())?;
+    bytes.write_f64_le(start_position.y())?;
+
+    let line_count = track.standard_lines().len() + track.scenery_lines().len();
+    bytes.write_u32_le(u32::try_from(line_count)?)?;
+
+    // Map endpoints to the first line id starting or ending there, for writing extension ids
+    let mut line_starts = HashMap::new();
+    let mut line_ends = HashMap::new();
+
+    for (line_id, line) in track.standard_lines().iter().enumerate() {
+        let line_id = i32::try_from(line_id)?;
+        line_starts
+            .entry(point_key(line.endpoints().p0()))
+            .or_insert(line_id);
+        line_ends
+            .entry(point_key(line.endpoints().p1()))
+            .or_insert(line_id);
+    }
+
+    for (line_id, line) in track.standard_lines().iter().enumerate() {
+        let line_type = if line.multiplier() == 0.0 {
+            LineType::Standard
+        } else {
+            LineType::Acceleration
+        };
+
+        let line_ext = u8::from(line.left_extension()) | (u8::from(line.right_extension()) << 1);
+        let type_flag = match line_type {
+            LineType::Standard => 1,
+            LineType::Acceleration => 2,
+            LineType::Scenery => 0,
+        };
+        bytes.write_u8(type_flag | (line_ext << 5) | (u8::from(line.flipped()) << 7))?;
+
+        if line_type == LineType::Acceleration && features.contains(&FEATURE_RED_MULTIPLIER) {
+            let multiplier = line.multiplier();
+            if multiplier.fract() != 0.0 || !(1.0..=255.0).contains(&multiplier) {
+                return Err(TrkWriteError::UnsupportedLineMultiplier(
+                    multiplier.to_string(),
+                ));
+            }
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            bytes.write_u8(multiplier as u8)?;
+        }
+
+        let line_id = i32::try_from(line_id)?;
+        bytes.write_i32_le(line_id)?;
+
+        if line_ext != 0 {
+            let prev_line_id = line_ends
+                .get(&point_key(line.endpoints().p0()))
+                .filter(|id| **id != line_id)
+                .copied()
+                .unwrap_or(-1);
+            let next_line_id = line_starts
+                .get(&point_key(line.endpoints().p1()))
+                .filter(|id| **id != line_id)
+                .copied()
+                .unwrap_or(-1);
+            bytes.write_i32_le(prev_line_id)?;
+            bytes.write_i32_le(next_line_id)?;
+        }
+
+        bytes.write_f64_le(line.x0())?;
+        bytes.write_f64_le(line.y0())?;
+        bytes.write_f64_le(line.x1())?;
+        bytes.write_f64_le(line.y1())?;
+    }
+
+    for line in track.scenery_lines() {
+        bytes.write_u8(0)?;
+
+        if features.contains(&FEATURE_SCENERY_WIDTH) {
+            bytes.write_u8(to_lra_scenery_width(line.width().unwrap_or(1.0)))?;
+        }
+
+        bytes.write_f64_le(line.x0())?;
+        bytes.write_f64_le(line.y0())?;
+        bytes.write_f64_le(line.x1())?;
+        bytes.write_f64_le(line.y1())?;
+    }
+
+    // Metadata section
+
+    let mut meta_entries = Vec::new();
+
+    if let Some(start_zoom) = track.start_zoom() {
+        meta_entries.push(format!(
+            "{}={}",
+            FEATURE_START_ZOOM,
+            to_lra_zoom(start_zoom)
+        ));
+    }
+
+    if let Some(start_gravity) = track.start_gravity() {
+        let start_gravity = to_lra_gravity(start_gravity);
+        #[expect(clippy::cast_possible_truncation)]
+        let (gravity_x, gravity_y) = (start_gravity.x() as f32, start_gravity.y() as f32);
+        meta_entries.push(format!("{}={}", FEATURE_Y_GRAVITY, gravity_y));
+        meta_entries.push(format!("{}={}", FEATURE_X_GRAVITY, gravity_x));
+    }
+
+    // The format only has a single well size for every line
+    let mut gravity_well_size = track.gravity_well_size();
+    if let Some(first_line) = track.standard_lines().first() {
+        let height = first_line.height();
+        for line in track.standard_lines() {
+            #[expect(clippy::float_cmp)]
+            if line.height() != height {
+                return Err(TrkWriteError::InconsistentLineHeight(format!(
+                    "{} != {}",
+                    line.height(),
+                    height
+                )));
+            }
+        }
+        match gravity_well_size {
+            #[expect(clippy::float_cmp)]
+            Some(well_size) if well_size != height => {
+                return Err(TrkWriteError::InconsistentLineHeight(format!(
+                    "gravity well size {} != line height {}",
+                    well_size, height
+                )));
+            }
+            #[expect(clippy::float_cmp)]
+            None if height != 10.0 => gravity_well_size = Some(height),
+            _ => {}
+        }
+    }
+
+    if let Some(gravity_well_size) = gravity_well_size {
+        meta_entries.push(format!(
+            "{}={}",
+            FEATURE_GRAVITY_WELL_SIZE, gravity_well_size
+        ));
+    }
+
+    if let Some(color) = track.start_background_color() {
+        meta_entries.push(format!("{}={}", FEATURE_BACKGROUND_COLOR_R, color.red()));
+        meta_entries.push(format!("{}={}", FEATURE_BACKGROUND_COLOR_G, color.green()));
+        meta_entries.push(format!("{}={}", FEATURE_BACKGROUND_COLOR_B, color.blue()));
+    }
+
+    if let Some(color) = track.start_line_color() {
+        meta_entries.push(format!("{}={}", FEATURE_LINE_COLOR_R, color.red()));
+        meta_entries.push(format!("{}={}", FEATURE_LINE_COLOR_G, color.green()));
+        meta_entries.push(format!("{}={}", FEATURE_LINE_COLOR_B, color.blue()));
+    }
+
+    if !track.triggers().is_empty() {
+        let triggers: Vec<String> = track
+            .triggers()
+            .iter()
+            .map(|trigger| match trigger.event() {
+                TriggerEvent::Zoom(target_zoom) => format!(
+                    "0:{}:{}:{}",
+                    to_lra_zoom(target_zoom),
+                    trigger.start_frame(),
+                    trigger.end_frame()
+                ),
+                TriggerEvent::BackgroundColor(color) => format!(
+                    "1:{}:{}:{}:{}:{}",
+                    color.red(),
+                    color.green(),
+                    color.blue(),
+                    trigger.start_frame(),
+                    trigger.end_frame()
+                ),
+                TriggerEvent::LineColor(color) => format!(
+                    "2:{}:{}:{}:{}:{}",
+                    color.red(),
+                    color.green(),
+                    color.blue(),
+                    trigger.start_frame(),
+                    trigger.end_frame()
+                ),
+            })
+            .collect();
+        meta_entries.push(format!("{}={}", FEATURE_TRIGGERS, triggers.join("&")));
+    }
+
+    if !meta_entries.is_empty() {
+        bytes.write_all(b"META")?;
+        bytes.write_u16_le(u16::try_from(meta_entries.len())?)?;
+
+        for meta_string in meta_entries {
+            bytes.write_u16_le(u16::try_from(meta_string.len())?)?;
+            bytes.write_all(meta_string.as_bytes())?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn point_key(point: Point) -> (u64, u64) {
+    (point.x().to_bits(), point.y().to_bits())
+}
@@ -1,9 +1,10 @@
 #[cfg(test)]
 mod test {
+    use color::RGBColor;
     use geometry::{Line, Point};
     use lr_format_core::{
         GridVersion, RemountVersion, RiderBuilder, SceneryLineBuilder, StandardLineBuilder,
-        TrackBuilder,
+        TrackBuilder, TriggerBuilder, TriggerEvent,
     };
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -60,6 +61,12 @@ mod test {
         let mut expected = TrackBuilder::new(GridVersion::V6_1);
         expected.audio_filename("Really_Long_Song_Name_That_Takes_Up_More_Than_128_Characters_To_Test_7BitEncodedInt_Overflow_0123456789012345678901234567890123456789.ogg".to_string());
         expected.audio_offset_until_start(-1.5);
+        expected.start_zoom(1.0);
+        expected.start_gravity(Vector2Df::new(0.175, 0.0));
+        expected.gravity_well_size(5.0);
+        expected.frictionless(true);
+        expected.start_background_color(RGBColor::new(1, 2, 3));
+        expected.start_line_color(RGBColor::new(4, 5, 6));
 
         let mut rider = RiderBuilder::new(RemountVersion::LRA);
         rider.start_offset(Vector2Df::zero());
@@ -94,6 +101,68 @@ mod test {
         line.width(3.0);
         expected.scenery_lines().push(line);
 
+        expected
+            .triggers()
+            .push(TriggerBuilder::new(TriggerEvent::Zoom(0.0), 57, 97));
+        expected.triggers().push(TriggerBuilder::new(
+            TriggerEvent::BackgroundColor(RGBColor::new(23, 23, 23)),
+            57,
+            87,
+        ));
+        expected.triggers().push(TriggerBuilder::new(
+            TriggerEvent::LineColor(RGBColor::new(2, 2, 2)),
+            57,
+            67,
+        ));
+
         assert_eq!(result, expected.build());
     }
+
+    #[test]
+    fn roundtrip() {
+        for entry in fs::read_dir("../fixtures/lr_format_trk").expect("Failed to read fixtures") {
+            let file_name = entry.expect("Failed to read fixture entry").path();
+            let file = fs::read(&file_name).expect("Failed to read TRK file");
+            let track = lr_format_trk::read(&file).expect("Failed to parse track file");
+            let written = lr_format_trk::write(&track).expect("Failed to write track file");
+            let result = lr_format_trk::read(&written).expect("Failed to parse written file");
+            assert_eq!(result, track, "{}", file_name.display());
+        }
+    }
+
+    #[test]
+    fn frictionless() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        track.riders().push(RiderBuilder::new(RemountVersion::None));
+        track.frictionless(true);
+        let track = track.build();
+
+        let written = lr_format_trk::write(&track).expect("Failed to write track file");
+        let result = lr_format_trk::read(&written).expect("Failed to parse written file");
+        assert!(result.frictionless());
+
+        let written = lr_format_trk::write(&TrackBuilder::new(GridVersion::V6_2).build())
+            .expect("Failed to write track file");
+        let result = lr_format_trk::read(&written).expect("Failed to parse written file");
+        assert!(!result.frictionless());
+    }
+
+    #[test]
+    fn conflicting_well_size() {
+        let mut track = TrackBuilder::new(GridVersion::V6_2);
+        track.riders().push(RiderBuilder::new(RemountVersion::None));
+        let mut line = StandardLineBuilder::new(Line::new(Point::zero(), Point::new(1.0, 0.0)));
+        line.height(5.0);
+        track.standard_lines().push(line);
+        track.gravity_well_size(5.0);
+        let track = track.build();
+        lr_format_trk::write(&track).expect("Failed to write track file");
+
+        let mut track = TrackBuilder::from(track);
+        track.gravity_well_size(20.0);
+        assert!(matches!(
+            lr_format_trk::write(&track.build()),
+            Err(lr_format_trk::TrkWriteError::InconsistentLineHeight(_))
+        ));
+    }
 }
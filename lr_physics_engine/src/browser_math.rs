@@ -0,0 +1,144 @@
+//! Trig functions that return the same bits as linerider.com's `Math` functions
+//!
+//! Rust's `f64` methods call the platform's libm, so they can't reproduce what a browser computes.
+//! V8 implements `Math.sin` and `Math.cos` with a port of fdlibm 5.3, while SpiderMonkey uses
+//! FreeBSD's msun, whose kernels round differently in the last bit. Both share fdlibm's `atan2`.
+
+mod fdlibm;
+mod msun;
+
+use std::f64::consts::PI;
+
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
+
+/// JavaScript engine whose `Math` results get reproduced
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum JsEngine {
+    /// Chrome, Edge and other Chromium browsers
+    #[default]
+    V8,
+    /// Firefox
+    SpiderMonkey,
+}
+
+impl JsEngine {
+    /// Equivalent of JavaScript's `Math.sin`
+    pub fn sin(self, x: f64) -> f64 {
+        match self {
+            JsEngine::V8 => fdlibm::sin(x),
+            JsEngine::SpiderMonkey => msun::sin(x),
+        }
+    }
+
+    /// Equivalent of JavaScript's `Math.cos`
+    pub fn cos(self, x: f64) -> f64 {
+        match self {
+            JsEngine::V8 => fdlibm::cos(x),
+            JsEngine::SpiderMonkey => msun::cos(x),
+        }
+    }
+
+    /// Equivalent of JavaScript's `Math.atan2`
+    pub fn atan2(self, y: f64, x: f64) -> f64 {
+        match self {
+            JsEngine::V8 | JsEngine::SpiderMonkey => fdlibm::atan2(y, x),
+        }
+    }
+
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_u8(match self {
+            JsEngine::V8 => 0,
+            JsEngine::SpiderMonkey => 1,
+        });
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        match reader.read_u8()? {
+            0 => Ok(JsEngine::V8),
+            1 => Ok(JsEngine::SpiderMonkey),
+            value => Err(Error::InvalidSnapshot(format!(
+                "Unknown JS engine: {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Equivalent of JavaScript's `degrees * Math.PI / 180`, evaluated left to right
+pub fn degrees_to_radians(degrees: f64) -> f64 {
+    degrees * PI / 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use crate::browser_math::{JsEngine, degrees_to_radians};
+
+    // Expected values are what Math.sin, Math.cos and Math.atan2 print in the browser
+    #[test]
+    #[expect(clippy::float_cmp)]
+    fn matches_browser_results() {
+        for engine in [JsEngine::V8, JsEngine::SpiderMonkey] {
+            assert_eq!(engine.sin(PI), 1.224_646_799_147_353_2e-16);
+            assert_eq!(engine.cos(FRAC_PI_2), 6.123_233_995_736_766e-17);
+            assert_eq!(engine.sin(1e22), -0.852_200_849_767_188_8);
+            assert_eq!(engine.atan2(1.0, 1.0), FRAC_PI_4);
+            assert_eq!(engine.atan2(-0.0, -1.0), -PI);
+            assert_eq!(
+                engine.sin(degrees_to_radians(30.0)),
+                0.499_999_999_999_999_94
+            );
+            assert_eq!(
+                engine.cos(degrees_to_radians(60.0)),
+                0.500_000_000_000_000_1
+            );
+        }
+
+        // The FreeBSD kernels round these differently
+        assert_eq!(
+            JsEngine::V8.sin(1.861_246_001_601_118_5),
+            0.958_115_193_123_603_2
+        );
+        assert_eq!(
+            JsEngine::V8.cos(2.336_965_801_331_700_3),
+            -0.693_380_163_088_195_4
+        );
+
+        // musl's atan2 doesn't snap steep angles to pi/2
+        assert_eq!(
+            JsEngine::V8.atan2(1.3, -7.732_161_248_718_924e-20),
+            FRAC_PI_2
+        );
+    }
+
+    #[test]
+    fn spidermonkey_matches_musl() {
+        // musl's sin and cos come from msun too, and only differ under other rounding modes
+        let mut x: f64 = 1e-9;
+        while x < 1e300 {
+            for value in [x, -x] {
+                let spidermonkey = JsEngine::SpiderMonkey;
+                assert_eq!(
+                    spidermonkey.sin(value).to_bits(),
+                    libm::sin(value).to_bits()
+                );
+                assert_eq!(
+                    spidermonkey.cos(value).to_bits(),
+                    libm::cos(value).to_bits()
+                );
+
+                let bits_apart = JsEngine::V8
+                    .sin(value)
+                    .to_bits()
+                    .abs_diff(libm::sin(value).to_bits());
+                assert!(bits_apart <= 1, "sin({value}) differs by {bits_apart} bits");
+            }
+            x *= 1.0137;
+        }
+    }
+}
@@ -0,0 +1,605 @@
+//! Port of the fdlibm 5.3 `sin`, `cos` and `atan2` used by V8's `base::ieee754`
+//!
+//! Kept as close to the C source as possible, including the order of every floating point
+//! operation, since any reordering can change the last bit of the result.
+
+pub(super) const PIO4_HIGH_WORD: i32 = 0x3FE9_21FB;
+const TINY_HIGH_WORD: i32 = 0x3E40_0000;
+pub(super) const NAN_OR_INF_HIGH_WORD: i32 = 0x7FF0_0000;
+
+pub(super) const HALF: f64 = 0.5;
+pub(super) const ONE: f64 = 1.0;
+const TWO24: f64 = f64::from_bits(0x4170_0000_0000_0000);
+const TWON24: f64 = f64::from_bits(0x3E70_0000_0000_0000);
+
+pub(super) const S1: f64 = f64::from_bits(0xBFC5_5555_5555_5549);
+pub(super) const S2: f64 = f64::from_bits(0x3F81_1111_1110_F8A6);
+pub(super) const S3: f64 = f64::from_bits(0xBF2A_01A0_19C1_61D5);
+pub(super) const S4: f64 = f64::from_bits(0x3EC7_1DE3_57B1_FE7D);
+pub(super) const S5: f64 = f64::from_bits(0xBE5A_E5E6_8A2B_9CEB);
+pub(super) const S6: f64 = f64::from_bits(0x3DE5_D93A_5ACF_D57C);
+
+pub(super) const C1: f64 = f64::from_bits(0x3FA5_5555_5555_554C);
+pub(super) const C2: f64 = f64::from_bits(0xBF56_C16C_16C1_5177);
+pub(super) const C3: f64 = f64::from_bits(0x3EFA_01A0_19CB_1590);
+pub(super) const C4: f64 = f64::from_bits(0xBE92_7E4F_809C_52AD);
+pub(super) const C5: f64 = f64::from_bits(0x3E21_EE9E_BDB4_B1C4);
+pub(super) const C6: f64 = f64::from_bits(0xBDA8_FAE9_BE88_38D4);
+
+const PI: f64 = f64::from_bits(0x4009_21FB_5444_2D18);
+const PI_LO: f64 = f64::from_bits(0x3CA1_A626_3314_5C07);
+const PI_O_2: f64 = f64::from_bits(0x3FF9_21FB_5444_2D18);
+const PI_O_4: f64 = f64::from_bits(0x3FE9_21FB_5444_2D18);
+
+pub(super) const ATAN_HI: [f64; 4] = [
+    f64::from_bits(0x3FDD_AC67_0561_BB4F),
+    f64::from_bits(0x3FE9_21FB_5444_2D18),
+    f64::from_bits(0x3FEF_730B_D281_F69B),
+    f64::from_bits(0x3FF9_21FB_5444_2D18),
+];
+const ATAN_LO: [f64; 4] = [
+    f64::from_bits(0x3C7A_2B7F_222F_65E2),
+    f64::from_bits(0x3C81_A626_3314_5C07),
+    f64::from_bits(0x3C70_0788_7AF0_CBBD),
+    f64::from_bits(0x3C91_A626_3314_5C07),
+];
+const AT: [f64; 11] = [
+    f64::from_bits(0x3FD5_5555_5555_550D),
+    f64::from_bits(0xBFC9_9999_9998_EBC4),
+    f64::from_bits(0x3FC2_4924_9200_83FF),
+    f64::from_bits(0xBFBC_71C6_FE23_1671),
+    f64::from_bits(0x3FB7_45CD_C54C_206E),
+    f64::from_bits(0xBFB3_B0F2_AF74_9A6D),
+    f64::from_bits(0x3FB1_0D66_A0D0_3D51),
+    f64::from_bits(0xBFAD_DE2D_52DE_FD9A),
+    f64::from_bits(0x3FA9_7B4B_2476_0DEB),
+    f64::from_bits(0xBFA2_B444_2C6A_6C2F),
+    f64::from_bits(0x3F90_AD3A_E322_DA11),
+];
+
+pub(super) const INVPIO2: f64 = f64::from_bits(0x3FE4_5F30_6DC9_C883);
+pub(super) const PIO2_1: f64 = f64::from_bits(0x3FF9_21FB_5440_0000);
+pub(super) const PIO2_1T: f64 = f64::from_bits(0x3DD0_B461_1A62_6331);
+pub(super) const PIO2_2: f64 = f64::from_bits(0x3DD0_B461_1A60_0000);
+pub(super) const PIO2_2T: f64 = f64::from_bits(0x3BA3_198A_2E03_7073);
+pub(super) const PIO2_3: f64 = f64::from_bits(0x3BA3_198A_2E00_0000);
+pub(super) const PIO2_3T: f64 = f64::from_bits(0x397B_839A_2520_49C1);
+
+/// High words of the first 32 multiples of pi/2
+const NPIO2_HW: [i32; 32] = [
+    0x3FF9_21FB,
+    0x4009_21FB,
+    0x4012_D97C,
+    0x4019_21FB,
+    0x401F_6A7A,
+    0x4022_D97C,
+    0x4025_FDBB,
+    0x4029_21FB,
+    0x402C_463A,
+    0x402F_6A7A,
+    0x4031_475C,
+    0x4032_D97C,
+    0x4034_6B9C,
+    0x4035_FDBB,
+    0x4037_8FDB,
+    0x4039_21FB,
+    0x403A_B41B,
+    0x403C_463A,
+    0x403D_D85A,
+    0x403F_6A7A,
+    0x4040_7E4C,
+    0x4041_475C,
+    0x4042_106C,
+    0x4042_D97C,
+    0x4043_A28C,
+    0x4044_6B9C,
+    0x4045_34AC,
+    0x4045_FDBB,
+    0x4046_C6CB,
+    0x4047_8FDB,
+    0x4048_58EB,
+    0x4049_21FB,
+];
+
+/// 24 bit chunks of 2/pi, enough for any finite double
+const TWO_OVER_PI: [i32; 66] = [
+    0xA2_F983, 0x6E_4E44, 0x15_29FC, 0x27_57D1, 0xF5_34DD, 0xC0_DB62, 0x95_993C, 0x43_9041,
+    0xFE_5163, 0xAB_DEBB, 0xC5_61B7, 0x24_6E3A, 0x42_4DD2, 0xE0_0649, 0x2E_EA09, 0xD1_921C,
+    0xFE_1DEB, 0x1C_B129, 0xA7_3EE8, 0x82_35F5, 0x2E_BB44, 0x84_E99C, 0x70_26B4, 0x5F_7E41,
+    0x39_91D6, 0x39_8353, 0x39_F49C, 0x84_5F8B, 0xBD_F928, 0x3B_1FF8, 0x97_FFDE, 0x05_980F,
+    0xEF_2F11, 0x8B_5A0A, 0x6D_1F6D, 0x36_7ECF, 0x27_CB09, 0xB7_4F46, 0x3F_669E, 0x5F_EA2D,
+    0x75_27BA, 0xC7_EBE5, 0xF1_7B3D, 0x07_39F7, 0x8A_5292, 0xEA_6BFB, 0x5F_B11F, 0x8D_5D08,
+    0x56_0330, 0x46_FC7B, 0x6B_ABF0, 0xCF_BC20, 0x9A_F436, 0x1D_A9E3, 0x91_615E, 0xE6_1B08,
+    0x65_9985, 0x5F_14A0, 0x68_408D, 0xFF_D880, 0x4D_7327, 0x31_0606, 0x15_56CA, 0x73_A8C9,
+    0x60_E27B, 0xC0_8C6B,
+];
+
+/// pi/2 split into 24 bit chunks
+const PIO2: [f64; 8] = [
+    f64::from_bits(0x3FF9_21FB_4000_0000),
+    f64::from_bits(0x3E74_442D_0000_0000),
+    f64::from_bits(0x3CF8_4698_8000_0000),
+    f64::from_bits(0x3B78_CC51_6000_0000),
+    f64::from_bits(0x39F0_1B83_8000_0000),
+    f64::from_bits(0x387A_2520_4000_0000),
+    f64::from_bits(0x36E3_8222_8000_0000),
+    f64::from_bits(0x3569_F31D_0000_0000),
+];
+
+pub(super) fn high_word(x: f64) -> i32 {
+    (x.to_bits() >> 32) as i32
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn low_word(x: f64) -> u32 {
+    x.to_bits() as u32
+}
+
+#[expect(clippy::cast_sign_loss)]
+fn from_words(high: i32, low: u32) -> f64 {
+    f64::from_bits((u64::from(high as u32) << 32) | u64::from(low))
+}
+
+/// Equivalent of C's `(int32_t) x`, which truncates toward zero
+#[expect(clippy::cast_possible_truncation)]
+pub(super) fn truncate(x: f64) -> i32 {
+    x as i32
+}
+
+/// Multiplies by 2^n, exact for every exponent the argument reduction needs
+#[expect(clippy::cast_sign_loss)]
+fn scalbn(x: f64, n: i32) -> f64 {
+    debug_assert!((-1022..=1023).contains(&n));
+    x * f64::from_bits(((0x3FF + n) as u64) << 52)
+}
+
+fn kernel_sin(x: f64, y: f64, iy: bool) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix < TINY_HIGH_WORD && truncate(x) == 0 {
+        return x;
+    }
+    let z = x * x;
+    let v = z * x;
+    let r = S2 + z * (S3 + z * (S4 + z * (S5 + z * S6)));
+    if iy {
+        x - ((z * (HALF * y - v * r) - y) - v * S1)
+    } else {
+        x + v * (S1 + z * r)
+    }
+}
+
+fn kernel_cos(x: f64, y: f64) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix < TINY_HIGH_WORD && truncate(x) == 0 {
+        return ONE;
+    }
+    let z = x * x;
+    let r = z * (C1 + z * (C2 + z * (C3 + z * (C4 + z * (C5 + z * C6)))));
+    if ix < 0x3FD3_3333 {
+        return ONE - (HALF * z - (z * r - x * y));
+    }
+    let qx = if ix > 0x3FE9_0000 {
+        0.28125
+    } else {
+        from_words(ix - 0x0020_0000, 0)
+    };
+    let hz = HALF * z - qx;
+    let a = ONE - qx;
+    a - (hz - (z * r - x * y))
+}
+
+/// Returns the quadrant of `x` and its remainder after dividing by pi/2, split in two doubles
+fn rem_pio2(x: f64) -> (i32, f64, f64) {
+    let hx = high_word(x);
+    let ix = hx & 0x7FFF_FFFF;
+
+    // |x| < 3pi/4, special case with n = +-1
+    if ix < 0x4002_D97C {
+        return if hx > 0 {
+            let mut z = x - PIO2_1;
+            if ix == 0x3FF9_21FB {
+                // Near pi/2, use 33 + 33 + 53 bit pi
+                z -= PIO2_2;
+                let y0 = z - PIO2_2T;
+                (1, y0, (z - y0) - PIO2_2T)
+            } else {
+                let y0 = z - PIO2_1T;
+                (1, y0, (z - y0) - PIO2_1T)
+            }
+        } else {
+            let mut z = x + PIO2_1;
+            if ix == 0x3FF9_21FB {
+                z += PIO2_2;
+                let y0 = z + PIO2_2T;
+                (-1, y0, (z - y0) + PIO2_2T)
+            } else {
+                let y0 = z + PIO2_1T;
+                (-1, y0, (z - y0) + PIO2_1T)
+            }
+        };
+    }
+
+    // |x| <= 2^19 * pi/2, medium size
+    if ix <= 0x4139_21FB {
+        let mut t = x.abs();
+        let n = truncate(t * INVPIO2 + HALF);
+        let fn_ = f64::from(n);
+        let mut r = t - fn_ * PIO2_1;
+        // First round is good to 85 bits
+        let mut w = fn_ * PIO2_1T;
+        let mut y0 = r - w;
+        let quick_check = usize::try_from(n - 1)
+            .ok()
+            .and_then(|index| NPIO2_HW.get(index))
+            .is_some_and(|&npio2_hw| ix != npio2_hw);
+        if !quick_check {
+            let j = ix >> 20;
+            let mut i = j - ((high_word(y0) >> 20) & 0x7FF);
+            if i > 16 {
+                // Second iteration, good to 118 bits
+                t = r;
+                w = fn_ * PIO2_2;
+                r = t - w;
+                w = fn_ * PIO2_2T - ((t - r) - w);
+                y0 = r - w;
+                i = j - ((high_word(y0) >> 20) & 0x7FF);
+                if i > 49 {
+                    // Third iteration, 151 bits covers all possible cases
+                    t = r;
+                    w = fn_ * PIO2_3;
+                    r = t - w;
+                    w = fn_ * PIO2_3T - ((t - r) - w);
+                    y0 = r - w;
+                }
+            }
+        }
+        let y1 = (r - y0) - w;
+        return if hx < 0 { (-n, -y0, -y1) } else { (n, y0, y1) };
+    }
+
+    rem_pio2_large(x)
+}
+
+/// Argument reduction for |x| > 2^19 * pi/2, shared with FreeBSD's msun
+pub(super) fn rem_pio2_large(x: f64) -> (i32, f64, f64) {
+    let hx = high_word(x);
+    let ix = hx & 0x7FFF_FFFF;
+
+    // Set z = scalbn(|x|, ilogb(x) - 23)
+    let e0 = (ix >> 20) - 1046;
+    let mut z = from_words(ix - (e0 << 20), low_word(x));
+    let tx0 = f64::from(truncate(z));
+    z = (z - tx0) * TWO24;
+    let tx1 = f64::from(truncate(z));
+    z = (z - tx1) * TWO24;
+    // Skip zero terms
+    let nx = if z != 0.0 {
+        3
+    } else if tx1 != 0.0 {
+        2
+    } else {
+        1
+    };
+    let (n, y0, y1) = kernel_rem_pio2(&[tx0, tx1, z], nx, e0);
+    if hx < 0 { (-n, -y0, -y1) } else { (n, y0, y1) }
+}
+
+/// Reduces a large argument split into `nx` 24 bit chunks `x` with exponent `e0`
+#[expect(
+    clippy::indexing_slicing,
+    clippy::cast_sign_loss,
+    clippy::integer_division
+)]
+fn kernel_rem_pio2(x: &[f64; 3], nx: usize, e0: i32) -> (i32, f64, f64) {
+    // Terms needed for double precision
+    const JK: usize = 4;
+    const JP: usize = JK;
+
+    let mut iq = [0_i32; 20];
+    let mut f = [0.0; 20];
+    let mut fq = [0.0; 20];
+    let mut q = [0.0; 20];
+
+    let jx = nx - 1;
+    let jv = ((e0 - 3) / 24).max(0);
+    let mut q0 = e0 - 24 * (jv + 1);
+    let jv = jv as usize;
+
+    // Set up f[0] to f[jx + jk] where f[jx + jk] = ipio2[jv + jk]
+    for (i, value) in f.iter_mut().enumerate().take(jx + JK + 1) {
+        *value = (jv + i)
+            .checked_sub(jx)
+            .map_or(0.0, |j| f64::from(TWO_OVER_PI[j]));
+    }
+
+    for i in 0..=JK {
+        let mut fw = 0.0;
+        for j in 0..=jx {
+            fw += x[j] * f[jx + i - j];
+        }
+        q[i] = fw;
+    }
+
+    let mut jz = JK;
+    let (mut z, mut n, ih) = loop {
+        // Distill q[] into iq[] reversingly
+        let mut z = q[jz];
+        for (i, j) in (1..=jz).rev().enumerate() {
+            let fw = f64::from(truncate(TWON24 * z));
+            iq[i] = truncate(z - TWO24 * fw);
+            z = q[j - 1] + fw;
+        }
+
+        // Compute n
+        z = scalbn(z, q0);
+        z -= 8.0 * (z * 0.125).floor();
+        let mut n = truncate(z);
+        z -= f64::from(n);
+        let ih = if q0 > 0 {
+            // Need iq[jz - 1] to determine n
+            let i = iq[jz - 1] >> (24 - q0);
+            n += i;
+            iq[jz - 1] -= i << (24 - q0);
+            iq[jz - 1] >> (23 - q0)
+        } else if q0 == 0 {
+            iq[jz - 1] >> 23
+        } else if z >= 0.5 {
+            2
+        } else {
+            0
+        };
+
+        if ih > 0 {
+            // q > 0.5
+            n += 1;
+            let mut carry = false;
+            for value in iq.iter_mut().take(jz) {
+                // Compute 1 - q
+                let j = *value;
+                if carry {
+                    *value = 0xFF_FFFF - j;
+                } else {
+                    carry = j != 0;
+                    if carry {
+                        *value = 0x100_0000 - j;
+                    }
+                }
+            }
+            match q0 {
+                1 => iq[jz - 1] &= 0x7F_FFFF,
+                2 => iq[jz - 1] &= 0x3F_FFFF,
+                _ => {}
+            }
+            if ih == 2 {
+                z = ONE - z;
+                if carry {
+                    z -= scalbn(ONE, q0);
+                }
+            }
+        }
+
+        // Check if recomputation is needed
+        if z == 0.0 && iq[JK..jz].iter().all(|&value| value == 0) {
+            let mut k = 1;
+            while iq[JK - k] == 0 {
+                k += 1;
+            }
+
+            // Add q[jz + 1] to q[jz + k]
+            for i in jz + 1..=jz + k {
+                f[jx + i] = f64::from(TWO_OVER_PI[jv + i]);
+                let mut fw = 0.0;
+                for j in 0..=jx {
+                    fw += x[j] * f[jx + i - j];
+                }
+                q[i] = fw;
+            }
+            jz += k;
+            continue;
+        }
+
+        break (z, n, ih);
+    };
+
+    // Chop off zero terms
+    if z == 0.0 {
+        jz -= 1;
+        q0 -= 24;
+        while iq[jz] == 0 {
+            jz -= 1;
+            q0 -= 24;
+        }
+    } else {
+        // Break z into 24 bit chunks if necessary
+        z = scalbn(z, -q0);
+        if z >= TWO24 {
+            let fw = f64::from(truncate(TWON24 * z));
+            iq[jz] = truncate(z - TWO24 * fw);
+            jz += 1;
+            q0 += 24;
+            iq[jz] = truncate(fw);
+        } else {
+            iq[jz] = truncate(z);
+        }
+    }
+
+    // Convert integer bit chunks to floating point values
+    let mut fw = scalbn(ONE, q0);
+    for i in (0..=jz).rev() {
+        q[i] = fw * f64::from(iq[i]);
+        fw *= TWON24;
+    }
+
+    // Compute PIo2[0, ..., jp] * q[jz, ..., 0]
+    for i in (0..=jz).rev() {
+        let mut fw = 0.0;
+        let mut k = 0;
+        while k <= JP && k <= jz - i {
+            fw += PIO2[k] * q[i + k];
+            k += 1;
+        }
+        fq[jz - i] = fw;
+    }
+
+    // Compress fq[] into two doubles
+    let mut fw = 0.0;
+    for i in (0..=jz).rev() {
+        fw += fq[i];
+    }
+    let y0 = if ih == 0 { fw } else { -fw };
+    fw = fq[0] - fw;
+    for value in fq.iter().take(jz + 1).skip(1) {
+        fw += value;
+    }
+    let y1 = if ih == 0 { fw } else { -fw };
+
+    n &= 7;
+    (n, y0, y1)
+}
+
+pub(super) fn sin(x: f64) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix <= PIO4_HIGH_WORD {
+        return kernel_sin(x, 0.0, false);
+    }
+    if ix >= NAN_OR_INF_HIGH_WORD {
+        return f64::NAN;
+    }
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => kernel_sin(y0, y1, true),
+        1 => kernel_cos(y0, y1),
+        2 => -kernel_sin(y0, y1, true),
+        _ => -kernel_cos(y0, y1),
+    }
+}
+
+pub(super) fn cos(x: f64) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix <= PIO4_HIGH_WORD {
+        return kernel_cos(x, 0.0);
+    }
+    if ix >= NAN_OR_INF_HIGH_WORD {
+        return f64::NAN;
+    }
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => kernel_cos(y0, y1),
+        1 => -kernel_sin(y0, y1, true),
+        2 => -kernel_cos(y0, y1),
+        _ => kernel_sin(y0, y1, true),
+    }
+}
+
+/// Unlike musl, |y/x| above 2^60 snaps to exactly +-pi/2 regardless of the sign of x
+pub(super) fn atan2(y: f64, x: f64) -> f64 {
+    let (hx, lx) = (high_word(x), low_word(x));
+    let (hy, ly) = (high_word(y), low_word(y));
+    let ix = hx & 0x7FFF_FFFF;
+    let iy = hy & 0x7FFF_FFFF;
+
+    if x.is_nan() || y.is_nan() {
+        return x + y;
+    }
+    if hx == 0x3FF0_0000 && lx == 0 {
+        return atan(y);
+    }
+    // 2 * sign(x) + sign(y)
+    let mut m = ((hy >> 31) & 1) | ((hx >> 30) & 2);
+
+    if iy == 0 && ly == 0 {
+        return match m {
+            0 | 1 => y,
+            2 => PI,
+            _ => -PI,
+        };
+    }
+    if ix == 0 && lx == 0 {
+        return if hy < 0 { -PI_O_2 } else { PI_O_2 };
+    }
+    if ix == NAN_OR_INF_HIGH_WORD {
+        return match (iy == NAN_OR_INF_HIGH_WORD, m) {
+            (true, 0) => PI_O_4,
+            (true, 1) => -PI_O_4,
+            (true, 2) => 3.0 * PI_O_4,
+            (true, _) => -3.0 * PI_O_4,
+            (false, 0) => 0.0,
+            (false, 1) => -0.0,
+            (false, 2) => PI,
+            (false, _) => -PI,
+        };
+    }
+    if iy == NAN_OR_INF_HIGH_WORD {
+        return if hy < 0 { -PI_O_2 } else { PI_O_2 };
+    }
+
+    let k = (iy - ix) >> 20;
+    let z = if k > 60 {
+        m &= 1;
+        PI_O_2 + 0.5 * PI_LO
+    } else if hx < 0 && k < -60 {
+        0.0
+    } else {
+        atan((y / x).abs())
+    };
+    match m {
+        0 => z,
+        1 => -z,
+        2 => PI - (z - PI_LO),
+        _ => (z - PI_LO) - PI,
+    }
+}
+
+#[expect(clippy::indexing_slicing)]
+fn atan(x: f64) -> f64 {
+    let hx = high_word(x);
+    let ix = hx & 0x7FFF_FFFF;
+
+    // |x| >= 2^66
+    if ix >= 0x4410_0000 {
+        if x.is_nan() {
+            return x + x;
+        }
+        return if hx > 0 {
+            ATAN_HI[3] + ATAN_LO[3]
+        } else {
+            -ATAN_HI[3] - ATAN_LO[3]
+        };
+    }
+
+    let (id, x) = if ix < 0x3FDC_0000 {
+        // |x| < 0.4375
+        if ix < TINY_HIGH_WORD {
+            return x;
+        }
+        (None, x)
+    } else {
+        let x = x.abs();
+        if ix < 0x3FE6_0000 {
+            // 7/16 <= |x| < 11/16
+            (Some(0), (2.0 * x - ONE) / (2.0 + x))
+        } else if ix < 0x3FF3_0000 {
+            // 11/16 <= |x| < 19/16
+            (Some(1), (x - ONE) / (x + ONE))
+        } else if ix < 0x4003_8000 {
+            // |x| < 2.4375
+            (Some(2), (x - 1.5) / (ONE + 1.5 * x))
+        } else {
+            // 2.4375 <= |x| < 2^66
+            (Some(3), -1.0 / x)
+        }
+    };
+
+    let z = x * x;
+    let w = z * z;
+    // Break sum from i = 0 to 10 of AT[i] * z^(i + 1) into odd and even polynomials
+    let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+    let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+    match id {
+        None => x - x * (s1 + s2),
+        Some(id) => {
+            let z = ATAN_HI[id] - ((x * (s1 + s2) - ATAN_LO[id]) - x);
+            if hx < 0 { -z } else { z }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Port of the FreeBSD msun `sin` and `cos` used by SpiderMonkey
+//!
+//! Shares fdlibm's constants and large argument reduction, but evaluates the kernel polynomials
+//! in a different order and reduces arguments up to 9pi/4 with precomputed multiples of pi/2.
+
+use crate::browser_math::fdlibm::{
+    C1, C2, C3, C4, C5, C6, HALF, INVPIO2, NAN_OR_INF_HIGH_WORD, ONE, PIO2_1, PIO2_1T, PIO2_2,
+    PIO2_2T, PIO2_3, PIO2_3T, PIO4_HIGH_WORD, S1, S2, S3, S4, S5, S6, high_word, rem_pio2_large,
+    truncate,
+};
+
+/// 1.5 / f64::EPSILON, adding and subtracting it rounds to the nearest integer
+const TO_INT: f64 = f64::from_bits(0x4338_0000_0000_0000);
+
+fn kernel_sin(x: f64, y: f64, iy: bool) -> f64 {
+    let z = x * x;
+    let w = z * z;
+    let r = S2 + z * (S3 + z * S4) + z * w * (S5 + z * S6);
+    let v = z * x;
+    if iy {
+        x - ((z * (HALF * y - v * r) - y) - v * S1)
+    } else {
+        x + v * (S1 + z * r)
+    }
+}
+
+fn kernel_cos(x: f64, y: f64) -> f64 {
+    let z = x * x;
+    let w = z * z;
+    let r = z * (C1 + z * (C2 + z * C3)) + w * w * (C4 + z * (C5 + z * C6));
+    let hz = HALF * z;
+    let w = ONE - hz;
+    w + (((ONE - w) - hz) + (z * r - x * y))
+}
+
+/// Reduces |x| <= 2^20 * pi/2 by rounding x / (pi/2) to the nearest integer
+fn rem_pio2_medium(x: f64, ix: i32) -> (i32, f64, f64) {
+    let fn_ = x * INVPIO2 + TO_INT - TO_INT;
+    let n = truncate(fn_);
+    let mut r = x - fn_ * PIO2_1;
+    // First round is good to 85 bits
+    let mut w = fn_ * PIO2_1T;
+    let mut y0 = r - w;
+    let ex = ix >> 20;
+    if ex - ((high_word(y0) >> 20) & 0x7FF) > 16 {
+        // Second round, good to 118 bits
+        let t = r;
+        w = fn_ * PIO2_2;
+        r = t - w;
+        w = fn_ * PIO2_2T - ((t - r) - w);
+        y0 = r - w;
+        if ex - ((high_word(y0) >> 20) & 0x7FF) > 49 {
+            // Third round, 151 bits covers all cases
+            let t = r;
+            w = fn_ * PIO2_3;
+            r = t - w;
+            w = fn_ * PIO2_3T - ((t - r) - w);
+            y0 = r - w;
+        }
+    }
+    (n, y0, (r - y0) - w)
+}
+
+/// Subtracts `n` multiples of pi/2 from x, one round good to 85 bits
+fn rem_pio2_small(x: f64, n: i32) -> (i32, f64, f64) {
+    let multiple = f64::from(n);
+    let z = x - multiple * PIO2_1;
+    let y0 = z - multiple * PIO2_1T;
+    (n, y0, (z - y0) - multiple * PIO2_1T)
+}
+
+fn rem_pio2(x: f64) -> (i32, f64, f64) {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    let sign = if x.is_sign_negative() { -1 } else { 1 };
+
+    // |x| ~<= 5pi/4, near pi/2 or 2pi/2 cancels too much for one round
+    if ix <= 0x400F_6A7A {
+        if (ix & 0xF_FFFF) == 0x9_21FB {
+            return rem_pio2_medium(x, ix);
+        }
+        return rem_pio2_small(x, sign * if ix <= 0x4002_D97C { 1 } else { 2 });
+    }
+    // |x| ~<= 9pi/4, near 3pi/2 or 4pi/2 cancels too much for one round
+    if ix <= 0x401C_463B {
+        if ix == 0x4012_D97C || ix == 0x4019_21FB {
+            return rem_pio2_medium(x, ix);
+        }
+        return rem_pio2_small(x, sign * if ix <= 0x4015_FDBC { 3 } else { 4 });
+    }
+    if ix < 0x4139_21FB {
+        return rem_pio2_medium(x, ix);
+    }
+    rem_pio2_large(x)
+}
+
+pub(super) fn sin(x: f64) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix <= PIO4_HIGH_WORD {
+        // |x| < 2^-26
+        if ix < 0x3E50_0000 {
+            return x;
+        }
+        return kernel_sin(x, 0.0, false);
+    }
+    if ix >= NAN_OR_INF_HIGH_WORD {
+        return f64::NAN;
+    }
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => kernel_sin(y0, y1, true),
+        1 => kernel_cos(y0, y1),
+        2 => -kernel_sin(y0, y1, true),
+        _ => -kernel_cos(y0, y1),
+    }
+}
+
+pub(super) fn cos(x: f64) -> f64 {
+    let ix = high_word(x) & 0x7FFF_FFFF;
+    if ix <= PIO4_HIGH_WORD {
+        // |x| < 2^-27 * sqrt(2)
+        if ix < 0x3E46_A09E {
+            return ONE;
+        }
+        return kernel_cos(x, 0.0);
+    }
+    if ix >= NAN_OR_INF_HIGH_WORD {
+        return f64::NAN;
+    }
+    let (n, y0, y1) = rem_pio2(x);
+    match n & 3 {
+        0 => kernel_cos(y0, y1),
+        1 => -kernel_sin(y0, y1, true),
+        2 => -kernel_cos(y0, y1),
+        _ => kernel_sin(y0, y1, true),
+    }
+}
@@ -0,0 +1,48 @@
+use geometry::Point;
+use vector2d::Vector2Df;
+
+/// Encodes values as explicit little endian bytes, shared by hashes and snapshots so both
+/// always see values the same way
+pub(crate) trait ByteSink {
+    fn write_bytes(&mut self, bytes: &[u8]);
+
+    fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(u8::from(value));
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    // Bit patterns are kept as is, so floats restore exactly and values that compare equal,
+    // like 0.0 and -0.0, are told apart
+    fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    fn write_point(&mut self, point: Point) {
+        self.write_f64(point.x());
+        self.write_f64(point.y());
+    }
+
+    fn write_vector(&mut self, vector: Vector2Df) {
+        self.write_f64(vector.x());
+        self.write_f64(vector.y());
+    }
+}
@@ -0,0 +1,176 @@
+use std::{iter::zip, ops::RangeInclusive};
+
+use vector2d::Vector2Df;
+
+use crate::{
+    Error, PhysicsEngine,
+    entity_registry::{EntityEvent, EntityPointId, EntityPointState, EntityState},
+};
+
+/// The first frame where an entity behaves differently between two engines
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    frame: u32,
+    entity_index: usize,
+    max_deviation: f64,
+    deviating_point_id: Option<EntityPointId>,
+    first_events: Vec<EntityEvent>,
+    second_events: Vec<EntityEvent>,
+}
+
+impl Divergence {
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Index of the entity within the states returned by `view_frame`
+    pub fn entity_index(&self) -> usize {
+        self.entity_index
+    }
+
+    /// Largest distance between the positions of a point in both engines, which can be 0.0 if
+    /// only velocities, mount phases, or sleds differ
+    pub fn max_deviation(&self) -> f64 {
+        self.max_deviation
+    }
+
+    /// The point with the largest deviation, if any point moved differently
+    pub fn deviating_point_id(&self) -> Option<EntityPointId> {
+        self.deviating_point_id
+    }
+
+    /// Events of the entity during the divergent frame in the first engine
+    pub fn first_events(&self) -> &[EntityEvent] {
+        &self.first_events
+    }
+
+    /// Events of the entity during the divergent frame in the second engine
+    pub fn second_events(&self) -> &[EntityEvent] {
+        &self.second_events
+    }
+
+    /// The first contacts that differ between both engines during the divergent frame, with
+    /// `None` for an engine whose contacts ran out first
+    ///
+    /// Returns `None` if both engines made the same contacts, meaning something other than a
+    /// contact caused the split, like a remount
+    pub fn split_contacts(&self) -> Option<(Option<EntityEvent>, Option<EntityEvent>)> {
+        let is_contact = |event: &&EntityEvent| matches!(event, EntityEvent::Contact { .. });
+        let mut first_contacts = self.first_events.iter().filter(is_contact);
+        let mut second_contacts = self.second_events.iter().filter(is_contact);
+
+        loop {
+            match (first_contacts.next(), second_contacts.next()) {
+                (None, None) => return None,
+                (first, second) if first != second => {
+                    return Some((first.copied(), second.copied()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Simulates two engines side by side and finds the first frame in `frames` where an entity's
+/// state differs, comparing the exact bit patterns of every point
+///
+/// Fails if the engines don't have the same number of entities
+pub fn find_divergence(
+    first: &mut PhysicsEngine,
+    second: &mut PhysicsEngine,
+    frames: RangeInclusive<u32>,
+) -> Result<Option<Divergence>, Error> {
+    for frame in frames {
+        let first_states = first.view_frame(frame);
+        let second_states = second.view_frame(frame);
+
+        if first_states.len() != second_states.len() {
+            return Err(Error::EntityCountMismatch(
+                first_states.len(),
+                second_states.len(),
+            ));
+        }
+
+        let divergent_entity = zip(&first_states, &second_states)
+            .position(|(first_state, second_state)| !same_state(first_state, second_state));
+
+        if let Some(entity_index) = divergent_entity {
+            let first_events = events_at(first, frame, entity_index);
+            let second_events = events_at(second, frame, entity_index);
+            let (max_deviation, deviating_point_id) = zip(&first_states, &second_states)
+                .nth(entity_index)
+                .map_or((0.0, None), |(first_state, second_state)| {
+                    max_deviation(first_state, second_state)
+                });
+
+            return Ok(Some(Divergence {
+                frame,
+                entity_index,
+                max_deviation,
+                deviating_point_id,
+                first_events,
+                second_events,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+fn same_state(first_state: &EntityState, second_state: &EntityState) -> bool {
+    let same_point = |first_point: &EntityPointState, second_point: &EntityPointState| {
+        same_bits(first_point.position(), second_point.position())
+            && same_bits(first_point.velocity(), second_point.velocity())
+            && same_bits(
+                first_point.computed_previous_position(),
+                second_point.computed_previous_position(),
+            )
+    };
+
+    first_state.mount_phase() == second_state.mount_phase()
+        && first_state.sled_intact() == second_state.sled_intact()
+        && first_state.point_states().len() == second_state.point_states().len()
+        && zip(first_state.point_states(), second_state.point_states()).all(
+            |((first_point_id, first_point), (second_point_id, second_point))| {
+                first_point_id == second_point_id && same_point(first_point, second_point)
+            },
+        )
+}
+
+// Unlike comparing floats, this tells apart 0.0 and -0.0, and matches NaN with itself
+fn same_bits(first: impl Into<Vector2Df>, second: impl Into<Vector2Df>) -> bool {
+    let (first, second) = (first.into(), second.into());
+    first.x().to_bits() == second.x().to_bits() && first.y().to_bits() == second.y().to_bits()
+}
+
+fn events_at(engine: &mut PhysicsEngine, frame: u32, entity_index: usize) -> Vec<EntityEvent> {
+    engine
+        .view_frame_events(frame)
+        .into_iter()
+        .nth(entity_index)
+        .unwrap_or_default()
+}
+
+fn max_deviation(
+    first_state: &EntityState,
+    second_state: &EntityState,
+) -> (f64, Option<EntityPointId>) {
+    let mut max_deviation = 0.0;
+    let mut deviating_point_id = None;
+
+    for (point_id, first_point) in first_state.point_states() {
+        let Some(second_point) = second_state.point_states().get(point_id) else {
+            continue;
+        };
+        let deviation = first_point
+            .position()
+            .distance_from(second_point.position());
+
+        if deviation > max_deviation {
+            max_deviation = deviation;
+            deviating_point_id = Some(*point_id);
+        }
+    }
+
+    (max_deviation, deviating_point_id)
+}
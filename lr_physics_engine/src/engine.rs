@@ -1,17 +1,29 @@
 use lr_physics_grid::GridVersion;
+use std::{collections::BTreeMap, ops::RangeInclusive};
 use vector2d::Vector2Df;
 
 use crate::{
-    PhysicsMoment,
+    CachePolicy, CacheUsage, Error, PhysicsMoment,
+    byte_sink::ByteSink as _,
     entity_registry::{
-        EntityId, EntityRegistry, EntityState, EntityTemplate, EntityTemplateId, Error,
+        EntityBoneId, EntityBoneState, EntityEvent, EntityId, EntityJointId, EntityPointId,
+        EntityRegistry, EntityState, EntityTemplate, EntityTemplateId,
     },
     line_registry::{LineId, LineRegistry, PhysicsLine},
+    snapshot::{MAGIC_NUMBER, SnapshotReader, SnapshotWriter, VERSION},
+    stable_hash::StableHasher,
+    trigger_registry::{PhysicsTrigger, TriggerId, TriggerRegistry},
+};
+#[cfg(feature = "lr_format_core")]
+use crate::{
+    entity_registry::{EntityTemplateBuilder, RemountVersion},
+    line_registry::PhysicsLineBuilder,
 };
 
 pub struct PhysicsEngine {
     line_registry: LineRegistry,
     entity_registry: EntityRegistry,
+    trigger_registry: TriggerRegistry,
 }
 
 // Engine holds the public API for the entity and line registries
@@ -21,18 +33,92 @@ impl PhysicsEngine {
         PhysicsEngine {
             line_registry: LineRegistry::new(grid_version),
             entity_registry: EntityRegistry::new(),
+            trigger_registry: TriggerRegistry::new(),
         }
     }
 
+    /// Builds an engine with the grid version, gravity, friction, lines, and riders of a track,
+    /// registering a default rider template for each remount version
+    ///
+    /// Track triggers are not mapped, since track formats have no gravity triggers
+    /// (see unsupported.md), so gravity triggers must be added with `add_trigger`
+    ///
+    /// Riders use their own remount version unless `remount_version` overrides it, like LRA
+    /// simulating every rider with its own remount rules
+    ///
+    /// Returns the engine and the id of each of the track's standard lines, in the same order
+    ///
+    /// Needs the `lr_format_core` feature
+    #[cfg(feature = "lr_format_core")]
+    pub fn from_track(
+        track: &lr_format_core::Track,
+        remount_version: Option<RemountVersion>,
+    ) -> (PhysicsEngine, Vec<LineId>) {
+        let mut engine = PhysicsEngine::new(GridVersion::from(track.grid_version()));
+        engine.set_frictionless(track.frictionless());
+
+        if let Some(gravity) = track.start_gravity() {
+            engine.set_gravity(gravity);
+        }
+
+        let line_ids = track
+            .standard_lines()
+            .iter()
+            .map(|line| engine.add_line(PhysicsLineBuilder::from(line).build()))
+            .collect();
+
+        let mut register_default_rider = |remount_version| {
+            engine.register_entity_template(EntityTemplateBuilder::default_rider(remount_version))
+        };
+        let template_none_id = register_default_rider(RemountVersion::None);
+        let template_comv1_id = register_default_rider(RemountVersion::ComV1);
+        let template_comv2_id = register_default_rider(RemountVersion::ComV2);
+        let template_lra_id = register_default_rider(RemountVersion::LRA);
+
+        for rider in track.riders() {
+            let template_id = match remount_version
+                .unwrap_or_else(|| RemountVersion::from(rider.remount_version()))
+            {
+                RemountVersion::None => template_none_id,
+                RemountVersion::ComV1 => template_comv1_id,
+                RemountVersion::ComV2 => template_comv2_id,
+                RemountVersion::LRA => template_lra_id,
+            };
+            // The template was just registered, so the entity is always created
+            engine.entity_registry.create_entity_with_initial_props(
+                template_id,
+                rider.start_offset(),
+                rider.start_velocity(),
+                rider.start_angle(),
+            );
+        }
+
+        (engine, line_ids)
+    }
+
     /// Provides a view of entities during a specific frame by simulating up to that frame
     pub fn view_frame(&mut self, frame: u32) -> Vec<EntityState> {
         self.view_moment(frame, PhysicsMoment::None)
     }
 
+    /// Provides the events of each entity during a specific frame by simulating up to that frame,
+    /// even if the event log is off
+    pub fn view_frame_events(&mut self, frame: u32) -> Vec<Vec<EntityEvent>> {
+        self.entity_registry.compute_frame_events(
+            frame,
+            &self.line_registry,
+            &self.trigger_registry,
+        )
+    }
+
     /// Provides a view of entities during a specific moment by simulating up to that frame and moment
     pub fn view_moment(&mut self, frame: u32, moment: PhysicsMoment) -> Vec<EntityState> {
-        self.entity_registry
-            .compute_frame(frame, moment, &self.line_registry)
+        self.entity_registry.compute_frame(
+            frame,
+            moment,
+            &self.line_registry,
+            &self.trigger_registry,
+        )
     }
 
     /// Changes the engine's grid version and reregisters all physics lines
@@ -41,24 +127,111 @@ impl PhysicsEngine {
         self.entity_registry.clear_cache();
     }
 
-    pub fn add_line(&mut self, line: PhysicsLine) -> LineId {
-        let id = self.line_registry.add_line(line);
+    /// Gets whether collisions ignore the contact friction of entity points
+    pub fn frictionless(&self) -> bool {
+        self.line_registry.frictionless()
+    }
+
+    /// Sets whether collisions ignore the contact friction of entity points,
+    /// like LRA tracks saved with the frictionless feature
+    pub fn set_frictionless(&mut self, frictionless: bool) {
+        self.line_registry.set_frictionless(frictionless);
         self.entity_registry.clear_cache();
-        id
+    }
+
+    /// Gets whether entity states record the events of the frame that produced them
+    pub fn event_log(&self) -> bool {
+        self.entity_registry.event_log()
+    }
+
+    /// Sets whether entity states record the contacts, bone breaks, and dismounts of the frame
+    /// that produced them, off by default since cached frames grow with the log
+    pub fn set_event_log(&mut self, event_log: bool) {
+        self.entity_registry.set_event_log(event_log);
+    }
+
+    /// Adds a trigger that changes physics over a range of frames
+    ///
+    /// Frames from the trigger's start frame onward are resimulated
+    pub fn add_trigger(&mut self, trigger: PhysicsTrigger) -> TriggerId {
+        self.invalidate_from_trigger(&trigger);
+        self.trigger_registry.add_trigger(trigger)
+    }
+
+    pub fn get_trigger(&self, id: TriggerId) -> Option<&PhysicsTrigger> {
+        self.trigger_registry.get_trigger(id)
+    }
+
+    pub fn replace_trigger(&mut self, id: TriggerId, new_trigger: PhysicsTrigger) {
+        if let Some(old_trigger) = self.trigger_registry.replace_trigger(id, new_trigger) {
+            self.invalidate_from_trigger(&old_trigger);
+            self.invalidate_from_trigger(&new_trigger);
+        }
+    }
+
+    pub fn remove_trigger(&mut self, id: TriggerId) {
+        if let Some(old_trigger) = self.trigger_registry.remove_trigger(id) {
+            self.invalidate_from_trigger(&old_trigger);
+        }
+    }
+
+    // Frames before a trigger starts are unaffected by it
+    fn invalidate_from_trigger(&mut self, trigger: &PhysicsTrigger) {
+        self.entity_registry
+            .invalidate_from(trigger.start_frame().saturating_sub(1));
+    }
+
+    /// Gets the gravity applied to entities whose template doesn't override it,
+    /// outside of gravity triggers
+    pub fn gravity(&self) -> Vector2Df {
+        self.entity_registry.gravity()
+    }
+
+    /// Sets the gravity applied to entities whose template doesn't override it
+    ///
+    /// Gravity points up in positive y, so the default is (0, -0.175)
+    pub fn set_gravity(&mut self, gravity: Vector2Df) {
+        self.entity_registry.set_gravity(gravity);
+    }
+
+    pub fn add_line(&mut self, line: PhysicsLine) -> LineId {
+        self.entity_registry
+            .invalidate_near_line(self.line_registry.cells_along(line.endpoints()));
+        self.line_registry.add_line(line)
     }
 
     pub fn get_line(&self, id: LineId) -> Option<&PhysicsLine> {
         self.line_registry.get_line(id)
     }
 
-    pub fn replace_line(&mut self, id: LineId, new_line: PhysicsLine) {
-        self.line_registry.replace_line(id, new_line);
-        self.entity_registry.clear_cache();
+    /// Replaces a line, preserving its id
+    ///
+    /// Errors if the line id is invalid
+    pub fn replace_line(&mut self, id: LineId, new_line: PhysicsLine) -> Result<(), Error> {
+        let old_endpoints = self
+            .line_registry
+            .get_line(id)
+            .ok_or(Error::LineNotFound(id))?
+            .endpoints();
+        self.entity_registry
+            .invalidate_near_line(self.line_registry.cells_along(old_endpoints));
+        self.entity_registry
+            .invalidate_near_line(self.line_registry.cells_along(new_line.endpoints()));
+        self.line_registry.replace_line(id, new_line)
     }
 
-    pub fn remove_line(&mut self, id: LineId) {
-        self.line_registry.remove_line(id);
-        self.entity_registry.clear_cache();
+    /// Removes a line
+    ///
+    /// Errors if the line id is invalid
+    pub fn remove_line(&mut self, id: LineId) -> Result<(), Error> {
+        let old_endpoints = self
+            .line_registry
+            .get_line(id)
+            .ok_or(Error::LineNotFound(id))?
+            .endpoints();
+        self.entity_registry
+            .invalidate_near_line(self.line_registry.cells_along(old_endpoints));
+        self.line_registry.remove_line(id)
     }
 
     pub fn register_entity_template(
@@ -113,6 +286,65 @@ impl PhysicsEngine {
             .set_entity_initial_velocity(entity_id, velocity)
     }
 
+    /// Gets an entity's initial rotation in degrees
+    ///
+    /// Returns the rotation if the entity id is valid
+    pub fn get_entity_initial_rotation(&self, entity_id: EntityId) -> Option<f64> {
+        self.entity_registry.get_entity_initial_rotation(entity_id)
+    }
+
+    /// Sets an entity's initial rotation in degrees around its initial offset, like linerider.com's start angle
+    ///
+    /// Errors if the entity id is invalid
+    pub fn set_entity_initial_rotation(
+        &mut self,
+        entity_id: EntityId,
+        rotation: f64,
+    ) -> Result<(), Error> {
+        self.entity_registry
+            .set_entity_initial_rotation(entity_id, rotation)
+    }
+
+    /// Looks up a point of an entity's template by the name given to its builder
+    ///
+    /// Returns the point id if the entity id is valid and its template has a point with that name
+    pub fn get_entity_point_id(&self, entity_id: EntityId, name: &str) -> Option<EntityPointId> {
+        self.entity_registry.get_entity_point_id(entity_id, name)
+    }
+
+    /// Provides a view of an entity's bones during a specific frame by simulating up to that frame
+    ///
+    /// Returns the bone states keyed by bone id if the entity id is valid
+    pub fn view_entity_bones(
+        &mut self,
+        frame: u32,
+        entity_id: EntityId,
+    ) -> Option<BTreeMap<EntityBoneId, EntityBoneState>> {
+        self.entity_registry.compute_bone_states(
+            frame,
+            entity_id,
+            &self.line_registry,
+            &self.trigger_registry,
+        )
+    }
+
+    /// Provides a view of an entity's joint angles in radians during a specific frame by
+    /// simulating up to that frame, negative for joints bent past their break point
+    ///
+    /// Returns the angles keyed by joint id if the entity id is valid
+    pub fn view_entity_joint_angles(
+        &mut self,
+        frame: u32,
+        entity_id: EntityId,
+    ) -> Option<BTreeMap<EntityJointId, f64>> {
+        self.entity_registry.compute_joint_angles(
+            frame,
+            entity_id,
+            &self.line_registry,
+            &self.trigger_registry,
+        )
+    }
+
     /// Removes an entity instance from the registry
     ///
     /// Errors if the entity id is invalid
@@ -120,8 +352,144 @@ impl PhysicsEngine {
         self.entity_registry.remove_entity(entity_id)
     }
 
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.entity_registry.cache_policy()
+    }
+
+    /// Changes which simulated frames are kept in memory, clearing the cached frames
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.entity_registry.set_cache_policy(policy);
+    }
+
+    /// Gets how many frames are cached and roughly how much memory they use
+    pub fn cache_usage(&self) -> CacheUsage {
+        self.entity_registry.cache_usage()
+    }
+
+    /// Gets the most threads that entity physics are split between
+    pub fn max_threads(&self) -> usize {
+        self.entity_registry.max_threads()
+    }
+
+    /// Sets the most threads that entity physics are split between, defaulting to the available
+    /// parallelism
+    ///
+    /// Only tracks with many entities are split, and results are identical for any thread count
+    pub fn set_max_threads(&mut self, max_threads: usize) {
+        self.entity_registry.set_max_threads(max_threads);
+    }
+
     /// Completely clears the state cache of all entities in the registry
     pub fn clear_cache(&mut self) {
         self.entity_registry.clear_cache();
     }
+
+    /// Stable hash of the exact states of all entities at a frame, simulating up to it if needed
+    ///
+    /// Hashes stay the same across runs and platforms, so recorded hashes can be compared between
+    /// engine builds, grid versions, or track edits
+    pub fn frame_hash(&mut self, frame: u32) -> u64 {
+        let states = self.view_frame(frame);
+        let mut hasher = StableHasher::new();
+        hasher.write_usize(states.len());
+        for state in &states {
+            state.hash_into(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Stable hash chaining the frame hashes of every frame in `frames`, so it only matches
+    /// another rolling hash if every frame along the way matched
+    pub fn rolling_hash(&mut self, frames: RangeInclusive<u32>) -> u64 {
+        let mut hasher = StableHasher::new();
+        for frame in frames {
+            hasher.write_u64(self.frame_hash(frame));
+        }
+        hasher.finish()
+    }
+
+    /// Hashes the ids and physics props of all lines, which snapshots are checked against
+    pub fn line_hash(&self) -> u64 {
+        self.line_registry.hash()
+    }
+
+    /// Exports entity templates, entities, and the states of `frames` as a versioned binary
+    /// snapshot, simulating up to those frames if needed
+    ///
+    /// Lines and triggers aren't included, so importing requires an engine with the same lines
+    ///
+    /// Errors if a collection is too long for the snapshot format
+    pub fn export_snapshot(&mut self, frames: &[u32]) -> Result<Vec<u8>, Error> {
+        let mut writer = SnapshotWriter::new();
+        writer.write_bytes(MAGIC_NUMBER);
+        writer.write_u8(VERSION);
+        writer.write_u64(self.line_registry.hash());
+        writer.write_u64(self.trigger_registry.hash());
+        writer.write_u8(match self.line_registry.grid_version() {
+            GridVersion::V6_0 => 0,
+            GridVersion::V6_1 => 1,
+            GridVersion::V6_2 => 2,
+        });
+        writer.write_bool(self.line_registry.frictionless());
+        self.entity_registry.write_snapshot(
+            &mut writer,
+            frames,
+            &self.line_registry,
+            &self.trigger_registry,
+        )?;
+        Ok(writer.into_bytes())
+    }
+
+    /// Replaces all entity templates, entities, cached frames, and the event log setting with
+    /// those of a snapshot, keeping the current cache policy
+    ///
+    /// Fails without changing the engine if the snapshot is malformed, has an unsupported
+    /// version, or was exported with different lines, triggers, grid version, friction, or gravity
+    pub fn import_snapshot(&mut self, snapshot: &[u8]) -> Result<(), Error> {
+        let mut reader = SnapshotReader::new(snapshot);
+        if reader.read_array::<4>()? != *MAGIC_NUMBER {
+            return Err(Error::InvalidSnapshot("Invalid magic number".to_string()));
+        }
+        let version = reader.read_u8()?;
+        if version != VERSION {
+            return Err(Error::UnsupportedSnapshotVersion(version));
+        }
+        if reader.read_u64()? != self.line_registry.hash() {
+            return Err(Error::SnapshotLineMismatch);
+        }
+        if reader.read_u64()? != self.trigger_registry.hash() {
+            return Err(Error::SnapshotTriggerMismatch);
+        }
+        let grid_version = match reader.read_u8()? {
+            0 => GridVersion::V6_0,
+            1 => GridVersion::V6_1,
+            2 => GridVersion::V6_2,
+            value => {
+                return Err(Error::InvalidSnapshot(format!(
+                    "Invalid grid version: {}",
+                    value
+                )));
+            }
+        };
+        if grid_version != self.line_registry.grid_version() {
+            return Err(Error::SnapshotSettingMismatch("grid version".to_string()));
+        }
+        if reader.read_bool()? != self.line_registry.frictionless() {
+            return Err(Error::SnapshotSettingMismatch("friction".to_string()));
+        }
+        let mut entity_registry =
+            EntityRegistry::read_snapshot(&mut reader, self.entity_registry.cache_policy())?;
+        if entity_registry.gravity() != self.entity_registry.gravity() {
+            return Err(Error::SnapshotSettingMismatch("gravity".to_string()));
+        }
+        entity_registry.set_max_threads(self.entity_registry.max_threads());
+        if !reader.is_empty() {
+            return Err(Error::InvalidSnapshot(
+                "Unexpected trailing data".to_string(),
+            ));
+        }
+
+        self.entity_registry = entity_registry;
+        Ok(())
+    }
 }
@@ -1,33 +1,45 @@
 mod bone;
 mod entity;
+mod entity_event;
 mod entity_state;
 mod entity_template;
 mod joint;
 mod mount_phase;
 mod point;
 mod remount_version;
+mod skeleton_graph;
 
 use std::{
     collections::{BTreeMap, HashMap, VecDeque},
-    error, fmt,
+    fmt,
     hash::Hash,
     iter::zip,
+    num::NonZeroUsize,
+    panic, thread,
 };
 
 pub use bone::{EntityBone, EntityBoneBuilder};
 pub(crate) use entity::Entity;
-pub(crate) use entity_state::EntityPointState;
-pub use entity_state::EntityState;
+pub use entity_event::EntityEvent;
+pub use entity_state::{EntityBoneState, EntityPointState, EntityState};
 pub use entity_template::{
     EntityBoneId, EntityJointId, EntityPointId, EntityTemplate, EntityTemplateBuilder,
 };
 pub use joint::{EntityJoint, EntityJointBuilder};
+use lr_physics_grid::CellRange;
 pub use mount_phase::MountPhase;
 pub use point::{EntityPoint, EntityPointBuilder};
 pub use remount_version::RemountVersion;
 use vector2d::Vector2Df;
 
-use crate::{PhysicsMoment, line_registry::LineRegistry};
+use crate::{
+    CachePolicy, CacheUsage, Error, PhysicsMoment,
+    byte_sink::ByteSink as _,
+    frame_cache::FrameCache,
+    line_registry::LineRegistry,
+    snapshot::{SnapshotReader, SnapshotWriter},
+    trigger_registry::TriggerRegistry,
+};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EntityTemplateId(usize);
@@ -35,55 +47,140 @@ pub struct EntityTemplateId(usize);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EntityId(usize);
 
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 pub(crate) struct EntityRegistry {
     entity_templates: HashMap<EntityTemplateId, EntityTemplate>,
+    // Sled point pairs for every pair of templates whose riders can swap sleds
+    sled_mappings:
+        HashMap<(EntityTemplateId, EntityTemplateId), Vec<(EntityPointId, EntityPointId)>>,
     entities: BTreeMap<EntityId, Entity>,
+    // Ids are never reused, so stale ids can't refer to newer entities or templates
+    next_entity_template_id: usize,
+    next_entity_id: usize,
     latest_synced_frame: u32,
-}
-
-#[derive(Debug)]
-pub enum Error {
-    EntityNotFound(EntityId),
-}
-
-impl error::Error for Error {}
-
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Error::EntityNotFound(id) => {
-                write!(f, "Entity with id not found: {}", id.0)
-            }
-        }
-    }
+    frame_cache: FrameCache,
+    // Grid cells each entity queried lines from while simulating each cached frame,
+    // indexed like the entity caches and then by entity
+    line_query_cells: Vec<Vec<Option<CellRange>>>,
+    gravity: Vector2Df,
+    event_log: bool,
+    max_threads: usize,
 }
 
 const EXPECT_TEMPLATE_MSG: &str = "Entity should point to valid template";
+const DEFAULT_GRAVITY_MULTIPLIER: f64 = 0.175;
+// Fewer entities per thread spend more time spawning threads than simulating
+const MIN_ENTITIES_PER_THREAD: usize = 16;
 
 impl EntityRegistry {
     pub(crate) fn new() -> Self {
         Self {
             entity_templates: HashMap::new(),
+            sled_mappings: HashMap::new(),
             entities: BTreeMap::new(),
+            next_entity_template_id: 0,
+            next_entity_id: 0,
             latest_synced_frame: 0,
+            frame_cache: FrameCache::new(CachePolicy::default()),
+            line_query_cells: Vec::new(),
+            gravity: Vector2Df::down() * DEFAULT_GRAVITY_MULTIPLIER,
+            event_log: false,
+            max_threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    pub(crate) fn max_threads(&self) -> usize {
+        self.max_threads
+    }
+
+    // Results don't depend on the thread count, so cached frames stay valid
+    pub(crate) fn set_max_threads(&mut self, max_threads: usize) {
+        self.max_threads = max_threads.max(1);
+    }
+
+    pub(crate) fn gravity(&self) -> Vector2Df {
+        self.gravity
+    }
+
+    pub(crate) fn set_gravity(&mut self, gravity: Vector2Df) {
+        self.clear_cache();
+        self.gravity = gravity;
+    }
+
+    pub(crate) fn event_log(&self) -> bool {
+        self.event_log
+    }
+
+    pub(crate) fn set_event_log(&mut self, event_log: bool) {
+        self.clear_cache();
+        self.event_log = event_log;
+
+        for entity in self.entities.values_mut() {
+            let template = self
+                .entity_templates
+                .get(&entity.template_id())
+                .expect(EXPECT_TEMPLATE_MSG);
+            entity.set_event_log(event_log, template);
         }
     }
 
     pub(crate) fn add_entity_template(&mut self, template: EntityTemplate) -> EntityTemplateId {
-        let id = EntityTemplateId(self.entity_templates.len());
+        let id = EntityTemplateId(self.next_entity_template_id);
+        self.next_entity_template_id += 1;
+
+        if let Some(mapping) = template.sled_point_mapping(&template) {
+            self.sled_mappings.insert((id, id), mapping);
+        }
+        #[expect(clippy::iter_over_hash_type)]
+        for (other_id, other_template) in &self.entity_templates {
+            if let Some(mapping) = template.sled_point_mapping(other_template) {
+                self.sled_mappings.insert((id, *other_id), mapping);
+            }
+            if let Some(mapping) = other_template.sled_point_mapping(&template) {
+                self.sled_mappings.insert((*other_id, id), mapping);
+            }
+        }
+
         self.entity_templates.insert(id, template);
         id
     }
 
     pub(crate) fn create_entity(&mut self, template_id: EntityTemplateId) -> Option<EntityId> {
+        self.create_entity_with_initial_props(template_id, None, None, None)
+    }
+
+    /// Creates an entity with any of its initial props set up front, like a track's rider
+    pub(crate) fn create_entity_with_initial_props(
+        &mut self,
+        template_id: EntityTemplateId,
+        offset: Option<Vector2Df>,
+        velocity: Option<Vector2Df>,
+        rotation: Option<f64>,
+    ) -> Option<EntityId> {
         self.clear_cache();
-        let template = self.entity_templates.get(&template_id);
-        template.map(|template| {
-            let entity = Entity::new(template_id, template);
-            let id = EntityId(self.entities.len());
-            self.entities.insert(id, entity);
-            id
-        })
+        let template = self.entity_templates.get(&template_id)?;
+        let mut entity = Entity::new(template_id, template);
+        if self.event_log {
+            entity.set_event_log(true, template);
+        }
+        if let Some(offset) = offset {
+            entity.set_initial_offset(offset, template);
+        }
+        if let Some(velocity) = velocity {
+            entity.set_initial_velocity(velocity, template);
+        }
+        if let Some(rotation) = rotation {
+            entity.set_initial_rotation(rotation, template);
+        }
+        let id = EntityId(self.next_entity_id);
+        self.next_entity_id += 1;
+        self.entities.insert(id, entity);
+        Some(id)
     }
 
     pub(crate) fn get_entity_initial_offset(&self, entity_id: EntityId) -> Option<Vector2Df> {
@@ -134,6 +231,84 @@ impl EntityRegistry {
         Ok(())
     }
 
+    pub(crate) fn get_entity_initial_rotation(&self, entity_id: EntityId) -> Option<f64> {
+        self.entities
+            .get(&entity_id)
+            .map(|entity| entity.initial_rotation())
+    }
+
+    pub(crate) fn set_entity_initial_rotation(
+        &mut self,
+        entity_id: EntityId,
+        rotation: f64,
+    ) -> Result<(), Error> {
+        self.clear_cache();
+        let entity = self
+            .entities
+            .get_mut(&entity_id)
+            .ok_or(Error::EntityNotFound(entity_id))?;
+        let template = self
+            .entity_templates
+            .get(&entity.template_id())
+            .expect(EXPECT_TEMPLATE_MSG);
+        entity.set_initial_rotation(rotation, template);
+        Ok(())
+    }
+
+    pub(crate) fn get_entity_point_id(
+        &self,
+        entity_id: EntityId,
+        name: &str,
+    ) -> Option<EntityPointId> {
+        self.entities.get(&entity_id).and_then(|entity| {
+            self.entity_templates
+                .get(&entity.template_id())
+                .expect(EXPECT_TEMPLATE_MSG)
+                .point_id(name)
+        })
+    }
+
+    /// Computes the bone states of an entity during a frame
+    pub(crate) fn compute_bone_states(
+        &mut self,
+        frame: u32,
+        entity_id: EntityId,
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+    ) -> Option<BTreeMap<EntityBoneId, EntityBoneState>> {
+        let state_index = self.entities.keys().position(|id| *id == entity_id)?;
+        let entity_states =
+            self.compute_frame(frame, PhysicsMoment::None, line_registry, trigger_registry);
+        let entity = self.entities.get(&entity_id)?;
+        let template = self
+            .entity_templates
+            .get(&entity.template_id())
+            .expect(EXPECT_TEMPLATE_MSG);
+        entity_states
+            .get(state_index)
+            .map(|state| state.bone_states(template, entity.bone_rest_lengths()))
+    }
+
+    pub(crate) fn compute_joint_angles(
+        &mut self,
+        frame: u32,
+        entity_id: EntityId,
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+    ) -> Option<BTreeMap<EntityJointId, f64>> {
+        let state_index = self.entities.keys().position(|id| *id == entity_id)?;
+        let entity_states =
+            self.compute_frame(frame, PhysicsMoment::None, line_registry, trigger_registry);
+        let entity = self.entities.get(&entity_id)?;
+        let template = self
+            .entity_templates
+            .get(&entity.template_id())
+            .expect(EXPECT_TEMPLATE_MSG);
+        entity_states
+            .get(state_index)
+            .map(|state| state.joint_angles(template))
+    }
+
     pub(crate) fn remove_entity(&mut self, entity_id: EntityId) -> Result<(), Error> {
         self.clear_cache();
         let removed_entity = self.entities.remove(&entity_id);
@@ -144,76 +319,447 @@ impl EntityRegistry {
         }
     }
 
+    /// Invalidates every cached frame after `frame`, keeping earlier frames
+    pub(crate) fn invalidate_from(&mut self, frame: u32) {
+        self.latest_synced_frame = self.latest_synced_frame.min(frame);
+    }
+
+    /// Invalidates cached frames starting from the first one where an entity queried a grid cell
+    /// a changed line registers in
+    pub(crate) fn invalidate_near_line(&mut self, line_cells: CellRange) {
+        let first_affected_index = self
+            .line_query_cells
+            .iter()
+            .take(self.latest_synced_frame as usize)
+            .position(|entity_cells| {
+                entity_cells
+                    .iter()
+                    .flatten()
+                    .any(|cells| cells.overlaps(&line_cells))
+            });
+
+        if let Some(index) = first_affected_index {
+            // Index i holds frame i + 1, so frame i is the last unaffected frame
+            #[expect(clippy::cast_possible_truncation)]
+            self.invalidate_from(index as u32);
+        }
+    }
+
     pub(crate) fn clear_cache(&mut self) {
         self.latest_synced_frame = 0;
-        for entity in self.entities.values_mut() {
-            entity.truncate_cache(0);
-        }
+        self.line_query_cells.clear();
+        self.frame_cache.clear();
     }
 
-    // This is a pretty delicate function that manages entity states and cache
-    pub(crate) fn compute_frame(
+    /// Writes templates, entities, and the states of `frames`, simulating up to them if needed
+    pub(crate) fn write_snapshot(
         &mut self,
-        frame: u32,
-        _moment: PhysicsMoment,
+        writer: &mut SnapshotWriter,
+        frames: &[u32],
         line_registry: &LineRegistry,
-    ) -> Vec<EntityState> {
-        let mut entity_states = Vec::new();
+        trigger_registry: &TriggerRegistry,
+    ) -> Result<(), Error> {
+        // Frame 0 is always derived from initial props, so only later frames are worth restoring
+        let mut frames: Vec<u32> = frames.iter().copied().filter(|frame| *frame > 0).collect();
+        frames.sort_unstable();
+        frames.dedup();
+        let frame_states: Vec<Vec<EntityState>> = frames
+            .iter()
+            .map(|frame| {
+                self.compute_frame(*frame, PhysicsMoment::None, line_registry, trigger_registry)
+            })
+            .collect();
+
+        writer.write_vector(self.gravity);
+        writer.write_bool(self.event_log);
+
+        // Templates can't be removed, so their ids are restored by adding them in id order
+        let mut template_ids: Vec<&EntityTemplateId> = self.entity_templates.keys().collect();
+        template_ids.sort_unstable();
+        writer.write_len(template_ids.len())?;
+        for template_id in template_ids {
+            self.entity_templates
+                .get(template_id)
+                .expect(EXPECT_TEMPLATE_MSG)
+                .write_snapshot(writer)?;
+        }
 
-        for entity in self.entities.values_mut() {
-            entity.truncate_cache(self.latest_synced_frame);
-            let state = entity
-                .cached_states()
-                .last()
-                .unwrap_or(entity.initial_state())
-                .clone();
-            entity_states.push(state);
+        writer.write_usize(self.next_entity_id);
+        writer.write_len(self.entities.len())?;
+        for (entity_id, entity) in &self.entities {
+            writer.write_usize(entity_id.0);
+            writer.write_usize(entity.template_id().0);
+            entity.write_snapshot(writer);
         }
 
-        while self.latest_synced_frame < frame {
-            let mut dismounts = VecDeque::new();
+        // Line query cells up to the last restored frame let line edits invalidate it
+        let synced_frames = frames.last().map_or(0, |frame| *frame as usize);
+        writer.write_len(synced_frames)?;
+        for entity_cells in self.line_query_cells.iter().take(synced_frames) {
+            for cells in entity_cells {
+                writer.write_option(cells.as_ref(), SnapshotWriter::write_cell_range);
+            }
+        }
 
-            for (entity, state) in zip(self.entities.values(), &mut entity_states) {
-                let template = self
-                    .entity_templates
-                    .get(&entity.template_id())
-                    .expect(EXPECT_TEMPLATE_MSG);
+        writer.write_len(frames.len())?;
+        for (frame, states) in zip(frames, frame_states) {
+            writer.write_u32(frame);
+            for state in states {
+                state.write_snapshot(writer)?;
+            }
+        }
+
+        Ok(())
+    }
 
-                let dismounted = state.process_frame(template, line_registry);
+    /// Reads a registry written by `write_snapshot`, caching its frames under `cache_policy`
+    pub(crate) fn read_snapshot(
+        reader: &mut SnapshotReader,
+        cache_policy: CachePolicy,
+    ) -> Result<Self, Error> {
+        let mut registry = EntityRegistry::new();
+        registry.set_cache_policy(cache_policy);
+        registry.gravity = reader.read_vector()?;
+        registry.event_log = reader.read_bool()?;
+
+        for _ in 0..reader.read_len()? {
+            registry.add_entity_template(EntityTemplate::read_snapshot(reader)?);
+        }
 
-                dismounts.push_back(dismounted);
+        registry.next_entity_id = reader.read_usize()?;
+        for _ in 0..reader.read_len()? {
+            let entity_id = EntityId(reader.read_usize()?);
+            let template_id = EntityTemplateId(reader.read_usize()?);
+            let template = registry.entity_templates.get(&template_id).ok_or_else(|| {
+                Error::InvalidSnapshot(format!("Entity {} has no template", entity_id))
+            })?;
+            let entity = Entity::read_snapshot(reader, template_id, template, registry.event_log)?;
+            if entity_id.0 >= registry.next_entity_id
+                || registry.entities.insert(entity_id, entity).is_some()
+            {
+                return Err(Error::InvalidSnapshot(format!(
+                    "Invalid entity id: {}",
+                    entity_id
+                )));
             }
+        }
 
-            for (state_index, entity) in self.entities.values().enumerate() {
-                let template = self
-                    .entity_templates
-                    .get(&entity.template_id())
-                    .expect(EXPECT_TEMPLATE_MSG);
+        let synced_frames = reader.read_len()?;
+        for _ in 0..synced_frames {
+            let entity_cells = (0..registry.entities.len())
+                .map(|_| reader.read_option(SnapshotReader::read_cell_range))
+                .collect::<Result<_, _>>()?;
+            registry.line_query_cells.push(entity_cells);
+        }
+        registry.latest_synced_frame = u32::try_from(synced_frames)
+            .map_err(|_| Error::InvalidSnapshot("Too many synced frames".to_string()))?;
+
+        for _ in 0..reader.read_len()? {
+            let frame = reader.read_u32()?;
+            if frame == 0 || frame > registry.latest_synced_frame {
+                return Err(Error::InvalidSnapshot(format!(
+                    "Frame {} is outside of the synced frames",
+                    frame
+                )));
+            }
+            let states = registry
+                .entities
+                .values()
+                .map(|entity| {
+                    let template = registry
+                        .entity_templates
+                        .get(&entity.template_id())
+                        .expect(EXPECT_TEMPLATE_MSG);
+                    EntityState::read_snapshot(reader, template)
+                })
+                .collect::<Result<_, _>>()?;
+            registry.frame_cache.insert(frame, states);
+        }
+
+        Ok(registry)
+    }
+
+    pub(crate) fn cache_policy(&self) -> CachePolicy {
+        self.frame_cache.policy()
+    }
+
+    pub(crate) fn set_cache_policy(&mut self, policy: CachePolicy) {
+        // Query cells are kept for every synced frame, so the simulation stays synced
+        self.frame_cache.set_policy(policy);
+    }
+
+    pub(crate) fn cache_usage(&self) -> CacheUsage {
+        self.frame_cache.usage()
+    }
 
-                let mut state = entity_states
-                    .get(state_index)
-                    .expect("Index should be within bounds of entity state array")
-                    .clone();
+    /// Computes entity states at a moment within a frame
+    ///
+    /// Moments within a frame are simulated on top of the previous frame without being cached
+    pub(crate) fn compute_frame(
+        &mut self,
+        frame: u32,
+        moment: PhysicsMoment,
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+    ) -> Vec<EntityState> {
+        if moment == PhysicsMoment::None || frame == 0 {
+            return self.sync_to_frame(frame, line_registry, trigger_registry);
+        }
 
-                let dismounted = dismounts.pop_front().is_some_and(|d| d);
+        let mut entity_states = self.sync_to_frame(frame - 1, line_registry, trigger_registry);
+        let frame_gravity = self.gravity_at(frame, trigger_registry);
+        let mut line_query_cells = vec![None; entity_states.len()];
+        self.process_entity_frames(
+            &mut entity_states,
+            line_registry,
+            frame_gravity,
+            moment,
+            &mut line_query_cells,
+        );
 
-                // TODO entity_states is all skeletons and may not match template
-                if !dismounted {
-                    state.process_mount_phase(template, &mut entity_states);
-                }
+        entity_states
+    }
 
-                *entity_states
-                    .get_mut(state_index)
-                    .expect("Index should be within bounds of entity state array") = state;
+    /// Computes the events of each entity during a frame
+    ///
+    /// Without the event log, the frame is simulated again from the previous frame with logging,
+    /// leaving the cache untouched
+    pub(crate) fn compute_frame_events(
+        &mut self,
+        frame: u32,
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+    ) -> Vec<Vec<EntityEvent>> {
+        let entity_states = if self.event_log || frame == 0 {
+            self.sync_to_frame(frame, line_registry, trigger_registry)
+        } else {
+            let mut entity_states = self.sync_to_frame(frame - 1, line_registry, trigger_registry);
+            for state in &mut entity_states {
+                state.enable_event_log();
             }
+            let mut line_query_cells = vec![None; entity_states.len()];
+            self.simulate_frame(
+                frame,
+                &mut entity_states,
+                line_registry,
+                trigger_registry,
+                &mut line_query_cells,
+            );
+            entity_states
+        };
+
+        entity_states
+            .iter()
+            .map(|state| state.events().to_vec())
+            .collect()
+    }
+
+    fn gravity_at(&self, frame: u32, trigger_registry: &TriggerRegistry) -> Vector2Df {
+        trigger_registry.gravity_at(frame).unwrap_or(self.gravity)
+    }
 
-            for (entity, state) in zip(self.entities.values_mut(), entity_states.clone()) {
-                entity.push_to_cache(state);
+    // This is a pretty delicate function that manages entity states and cache
+    // Frames up to the latest synced frame are resimulated from the closest cached frame
+    fn sync_to_frame(
+        &mut self,
+        frame: u32,
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+    ) -> Vec<EntityState> {
+        self.frame_cache.truncate(self.latest_synced_frame);
+        self.line_query_cells
+            .truncate(self.latest_synced_frame as usize);
+
+        let (mut current_frame, mut entity_states) = match self
+            .frame_cache
+            .closest_at_or_before(frame.min(self.latest_synced_frame))
+        {
+            Some((cached_frame, states)) => (cached_frame, states.clone()),
+            None => (
+                0,
+                self.entities
+                    .values()
+                    .map(|entity| entity.initial_state().clone())
+                    .collect(),
+            ),
+        };
+
+        while current_frame < frame {
+            current_frame += 1;
+            let mut line_query_cells = vec![None; entity_states.len()];
+
+            self.simulate_frame(
+                current_frame,
+                &mut entity_states,
+                line_registry,
+                trigger_registry,
+                &mut line_query_cells,
+            );
+
+            if current_frame > self.latest_synced_frame {
+                self.line_query_cells.push(line_query_cells);
+                self.latest_synced_frame = current_frame;
             }
 
-            self.latest_synced_frame += 1;
+            self.frame_cache
+                .insert(current_frame, entity_states.clone());
         }
 
         entity_states
     }
+
+    // Transforms the previous frame's entity states into the states of `frame`
+    fn simulate_frame(
+        &self,
+        frame: u32,
+        entity_states: &mut [EntityState],
+        line_registry: &LineRegistry,
+        trigger_registry: &TriggerRegistry,
+        line_query_cells: &mut [Option<CellRange>],
+    ) {
+        let frame_gravity = self.gravity_at(frame, trigger_registry);
+        let mut dismounts = VecDeque::from(self.process_entity_frames(
+            entity_states,
+            line_registry,
+            frame_gravity,
+            PhysicsMoment::None,
+            line_query_cells,
+        ));
+
+        // Riders can swap sleds while remounting, so mount phases are processed in entity order
+        for (state_index, entity) in self.entities.values().enumerate() {
+            let template = self
+                .entity_templates
+                .get(&entity.template_id())
+                .expect(EXPECT_TEMPLATE_MSG);
+
+            let mut state = entity_states
+                .get(state_index)
+                .expect("Index should be within bounds of entity state array")
+                .clone();
+
+            let dismounted = dismounts.pop_front().is_some_and(|d| d);
+
+            if !dismounted {
+                let template_id = entity.template_id();
+                let other_states = zip(self.entities.values(), entity_states.iter_mut())
+                    .filter_map(|(other_entity, other_state)| {
+                        let other_template_id = other_entity.template_id();
+                        let other_template = self
+                            .entity_templates
+                            .get(&other_template_id)
+                            .expect(EXPECT_TEMPLATE_MSG);
+                        self.sled_mappings
+                            .get(&(template_id, other_template_id))
+                            .map(|sled_mapping| {
+                                (
+                                    other_state,
+                                    sled_mapping.as_slice(),
+                                    other_template.remount_version(),
+                                )
+                            })
+                    });
+                state.process_mount_phase(template, entity.bone_rest_lengths(), other_states);
+            }
+
+            *entity_states
+                .get_mut(state_index)
+                .expect("Index should be within bounds of entity state array") = state;
+        }
+    }
+
+    // Runs the physics step of every entity, which doesn't depend on other entities,
+    // splitting entities between threads when there are enough of them
+    // Returns whether each entity dismounted
+    fn process_entity_frames(
+        &self,
+        entity_states: &mut [EntityState],
+        line_registry: &LineRegistry,
+        frame_gravity: Vector2Df,
+        moment: PhysicsMoment,
+        line_query_cells: &mut [Option<CellRange>],
+    ) -> Vec<bool> {
+        let entities: Vec<&Entity> = self.entities.values().collect();
+        // Rounds down so every thread gets at least the minimum number of entities
+        #[expect(clippy::integer_division)]
+        let thread_count = self
+            .max_threads
+            .min(entities.len() / MIN_ENTITIES_PER_THREAD)
+            .max(1);
+
+        if thread_count == 1 {
+            return self.process_entity_chunk(
+                &entities,
+                entity_states,
+                line_registry,
+                frame_gravity,
+                moment,
+                line_query_cells,
+            );
+        }
+
+        let chunk_size = entities.len().div_ceil(thread_count);
+        let chunk_dismounts: Vec<Vec<bool>> = thread::scope(|scope| {
+            let handles: Vec<_> = zip(
+                zip(
+                    entities.chunks(chunk_size),
+                    entity_states.chunks_mut(chunk_size),
+                ),
+                line_query_cells.chunks_mut(chunk_size),
+            )
+            .map(|((entities, entity_states), line_query_cells)| {
+                scope.spawn(move || {
+                    self.process_entity_chunk(
+                        entities,
+                        entity_states,
+                        line_registry,
+                        frame_gravity,
+                        moment,
+                        line_query_cells,
+                    )
+                })
+            })
+            .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect()
+        });
+
+        chunk_dismounts.into_iter().flatten().collect()
+    }
+
+    fn process_entity_chunk(
+        &self,
+        entities: &[&Entity],
+        entity_states: &mut [EntityState],
+        line_registry: &LineRegistry,
+        frame_gravity: Vector2Df,
+        moment: PhysicsMoment,
+        line_query_cells: &mut [Option<CellRange>],
+    ) -> Vec<bool> {
+        zip(zip(entities, entity_states), line_query_cells)
+            .map(|((entity, state), entity_query_cells)| {
+                let template = self
+                    .entity_templates
+                    .get(&entity.template_id())
+                    .expect(EXPECT_TEMPLATE_MSG);
+
+                let gravity = template.gravity().unwrap_or(frame_gravity);
+                state.process_frame(
+                    template,
+                    entity.bone_rest_lengths(),
+                    line_registry,
+                    gravity,
+                    moment,
+                    entity_query_cells,
+                )
+            })
+            .collect()
+    }
 }
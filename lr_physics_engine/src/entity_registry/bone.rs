@@ -1,9 +1,12 @@
-use std::collections::BTreeMap;
-
 use geometry::Point;
 use vector2d::Vector2Df;
 
-use crate::entity_registry::{EntityPoint, EntityPointId, entity_state::EntityPointState};
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    entity_registry::{EntityPoint, EntityPointId, entity_state::EntityPointState},
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
 
 /// Computed properties when built
 struct Computed {
@@ -34,18 +37,48 @@ impl EntityBone {
         self.computed.is_flutter
     }
 
+    pub(crate) fn is_repel(&self) -> bool {
+        self.repel_only
+    }
+
     pub(crate) fn is_breakable(&self) -> bool {
         self.computed.is_breakable
     }
 
-    pub(crate) fn get_percent_adjustment(&self, bone_vector: Vector2Df) -> f64 {
+    /// Rest length computed from the template's initial point positions
+    pub(crate) fn rest_length(&self) -> f64 {
+        self.computed.rest_length
+    }
+
+    /// Rest length computed after translating the points by `offset`, which can round differently
+    pub(crate) fn rest_length_at_offset(
+        &self,
+        points: (&EntityPoint, &EntityPoint),
+        offset: Vector2Df,
+    ) -> f64 {
+        offset_rest_length(points, offset, self.initial_length_factor)
+    }
+
+    pub(crate) fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        self.point_ids.0.write_snapshot(writer);
+        self.point_ids.1.write_snapshot(writer);
+        writer.write_f64(self.bias);
+        writer.write_f64(self.initial_length_factor);
+        writer.write_bool(self.repel_only);
+        writer.write_f64(self.endurance);
+        writer.write_f64(self.adjustment_strength);
+        writer.write_f64(self.endurance_remount_factor);
+        writer.write_f64(self.adjustment_strength_remount_factor);
+    }
+
+    pub(crate) fn get_percent_adjustment(&self, bone_vector: Vector2Df, rest_length: f64) -> f64 {
         let current_length = bone_vector.length();
-        let should_repel = current_length < self.computed.rest_length;
+        let should_repel = current_length < rest_length;
 
         if current_length == 0.0 || (self.repel_only && !should_repel) {
             0.0
         } else {
-            (current_length - self.computed.rest_length) / current_length
+            (current_length - rest_length) / current_length
         }
     }
 
@@ -53,12 +86,13 @@ impl EntityBone {
         &self,
         point_states: (&EntityPointState, &EntityPointState),
         remounting: bool,
+        rest_length: f64,
     ) -> (Point, Point) {
         let bone_vector = point_states
             .0
             .position()
             .vector_from(point_states.1.position());
-        let percent_adjustment = self.get_percent_adjustment(bone_vector);
+        let percent_adjustment = self.get_percent_adjustment(bone_vector, rest_length);
 
         let adjustment_strength = if remounting {
             self.adjustment_strength * self.adjustment_strength_remount_factor
@@ -83,12 +117,13 @@ impl EntityBone {
         &self,
         point_states: (&EntityPointState, &EntityPointState),
         remounting: bool,
+        rest_length: f64,
     ) -> bool {
         let bone_vector = point_states
             .1
             .position()
             .vector_from(point_states.0.position());
-        let percent_adjustment = self.get_percent_adjustment(bone_vector);
+        let percent_adjustment = self.get_percent_adjustment(bone_vector, rest_length);
 
         let endurance = if remounting {
             self.endurance * self.endurance_remount_factor
@@ -96,7 +131,7 @@ impl EntityBone {
             self.endurance
         };
 
-        percent_adjustment <= endurance * self.computed.rest_length
+        percent_adjustment <= endurance * rest_length
     }
 }
 
@@ -161,15 +196,28 @@ impl EntityBoneBuilder {
         self
     }
 
-    pub fn build(self, point_map: &BTreeMap<EntityPointId, EntityPoint>) -> EntityBone {
-        let points = (
-            point_map
-                .get(&self.point_ids.0)
-                .expect("Building this bone should properly resolve builder points"),
-            point_map
-                .get(&self.point_ids.1)
-                .expect("Building this bone should properly resolve builder points"),
-        );
+    pub(crate) fn point_ids(&self) -> (EntityPointId, EntityPointId) {
+        self.point_ids
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityBoneBuilder {
+            point_ids: (
+                EntityPointId::read_snapshot(reader)?,
+                EntityPointId::read_snapshot(reader)?,
+            ),
+            bias: reader.read_f64()?,
+            initial_length_factor: reader.read_f64()?,
+            repel_only: reader.read_bool()?,
+            endurance: reader.read_f64()?,
+            adjustment_strength: reader.read_f64()?,
+            endurance_remount_factor: reader.read_f64()?,
+            adjustment_strength_remount_factor: reader.read_f64()?,
+        })
+    }
+
+    /// Builds with the points resolved from `point_ids` by the template
+    pub(crate) fn build(self, points: (&EntityPoint, &EntityPoint)) -> EntityBone {
         EntityBone {
             point_ids: self.point_ids,
             bias: self.bias,
@@ -181,11 +229,11 @@ impl EntityBoneBuilder {
             adjustment_strength_remount_factor: self.adjustment_strength_remount_factor,
             computed: Computed {
                 is_flutter: !(points.0.is_contact() && points.1.is_contact()),
-                rest_length: points
-                    .0
-                    .initial_position()
-                    .distance_from(points.1.initial_position())
-                    * self.initial_length_factor,
+                rest_length: offset_rest_length(
+                    points,
+                    Vector2Df::zero(),
+                    self.initial_length_factor,
+                ),
                 is_breakable: self.endurance < f64::INFINITY,
             },
         }
@@ -206,3 +254,16 @@ impl From<EntityBone> for EntityBoneBuilder {
         }
     }
 }
+
+fn offset_rest_length(
+    points: (&EntityPoint, &EntityPoint),
+    offset: Vector2Df,
+    initial_length_factor: f64,
+) -> f64 {
+    points
+        .0
+        .initial_position()
+        .translated_by(offset)
+        .distance_from(points.1.initial_position().translated_by(offset))
+        * initial_length_factor
+}
@@ -1,14 +1,23 @@
-use crate::entity_registry::{EntityState, EntityTemplate, EntityTemplateId};
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    entity_registry::{EntityState, EntityTemplate, EntityTemplateId},
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
 use vector2d::Vector2Df;
 
 struct InitialProps {
     offset: Vector2Df,
     velocity: Vector2Df,
+    rotation: f64,
+    // Follows the engine's event log setting rather than being set per entity
+    event_log: bool,
 }
 
 pub(crate) struct Entity {
-    cached_states: Vec<EntityState>,
     initial_state: EntityState,
+    // Indexed in bone id order, and only differs from the template for templates that compute them after offset
+    bone_rest_lengths: Vec<f64>,
     initial_props: InitialProps,
     associated_template_id: EntityTemplateId,
 }
@@ -16,34 +25,34 @@ pub(crate) struct Entity {
 impl Entity {
     pub(super) fn new(template_id: EntityTemplateId, template: &EntityTemplate) -> Self {
         let mut entity = Self {
-            cached_states: Vec::new(),
             initial_props: InitialProps {
                 offset: Vector2Df::zero(),
                 velocity: Vector2Df::zero(),
+                rotation: 0.0,
+                event_log: false,
             },
-            initial_state: EntityState::new(template, Vector2Df::zero(), Vector2Df::zero()),
+            initial_state: EntityState::new(
+                template,
+                Vector2Df::zero(),
+                Vector2Df::zero(),
+                0.0,
+                false,
+            ),
+            bone_rest_lengths: template.bone_rest_lengths(Vector2Df::zero()),
             associated_template_id: template_id,
         };
         entity.regenerate_initial_state(template);
         entity
     }
 
-    pub(super) fn cached_states(&self) -> &Vec<EntityState> {
-        &self.cached_states
-    }
-
-    pub(super) fn push_to_cache(&mut self, state: EntityState) {
-        self.cached_states.push(state);
-    }
-
-    pub(super) fn truncate_cache(&mut self, size: u32) {
-        self.cached_states.truncate(size as usize);
-    }
-
     pub(super) fn initial_state(&self) -> &EntityState {
         &self.initial_state
     }
 
+    pub(super) fn bone_rest_lengths(&self) -> &[f64] {
+        &self.bone_rest_lengths
+    }
+
     pub(super) fn initial_offset(&self) -> Vector2Df {
         self.initial_props.offset
     }
@@ -62,13 +71,55 @@ impl Entity {
         self.regenerate_initial_state(template);
     }
 
+    pub(super) fn initial_rotation(&self) -> f64 {
+        self.initial_props.rotation
+    }
+
+    pub(super) fn set_initial_rotation(&mut self, rotation: f64, template: &EntityTemplate) {
+        self.initial_props.rotation = rotation;
+        self.regenerate_initial_state(template);
+    }
+
+    pub(super) fn set_event_log(&mut self, event_log: bool, template: &EntityTemplate) {
+        self.initial_props.event_log = event_log;
+        self.regenerate_initial_state(template);
+    }
+
     pub(super) fn template_id(&self) -> EntityTemplateId {
         self.associated_template_id
     }
 
+    pub(super) fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        writer.write_vector(self.initial_props.offset);
+        writer.write_vector(self.initial_props.velocity);
+        writer.write_f64(self.initial_props.rotation);
+    }
+
+    pub(super) fn read_snapshot(
+        reader: &mut SnapshotReader,
+        template_id: EntityTemplateId,
+        template: &EntityTemplate,
+        event_log: bool,
+    ) -> Result<Self, Error> {
+        let mut entity = Self::new(template_id, template);
+        entity.initial_props = InitialProps {
+            offset: reader.read_vector()?,
+            velocity: reader.read_vector()?,
+            rotation: reader.read_f64()?,
+            event_log,
+        };
+        entity.regenerate_initial_state(template);
+        Ok(entity)
+    }
+
     fn regenerate_initial_state(&mut self, template: &EntityTemplate) {
-        self.initial_state =
-            EntityState::new(template, self.initial_offset(), self.initial_velocity());
-        self.cached_states.clear();
+        self.initial_state = EntityState::new(
+            template,
+            self.initial_offset(),
+            self.initial_velocity(),
+            self.initial_rotation(),
+            self.initial_props.event_log,
+        );
+        self.bone_rest_lengths = template.bone_rest_lengths(self.initial_offset());
     }
 }
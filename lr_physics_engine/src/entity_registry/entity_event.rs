@@ -0,0 +1,72 @@
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    entity_registry::{EntityBoneId, EntityJointId, EntityPointId},
+    line_registry::LineId,
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
+
+/// Something that happened to an entity while simulating a frame, recorded when the engine's event log is on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EntityEvent {
+    /// A contact point collided with a line during a bone iteration, starting from 0
+    Contact {
+        point_id: EntityPointId,
+        line_id: LineId,
+        iteration: u8,
+    },
+    /// A breakable bone stretched past its limit, dismounting the entity
+    BoneBreak(EntityBoneId),
+    /// A mount joint bent past its limit, dismounting the entity
+    Dismount(EntityJointId),
+    /// A sled joint bent past its limit, breaking the sled
+    SledBreak(EntityJointId),
+}
+
+impl EntityEvent {
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        match self {
+            EntityEvent::Contact {
+                point_id,
+                line_id,
+                iteration,
+            } => {
+                writer.write_u8(0);
+                point_id.write_snapshot(writer);
+                line_id.write_snapshot(writer);
+                writer.write_u8(iteration);
+            }
+            EntityEvent::BoneBreak(bone_id) => {
+                writer.write_u8(1);
+                bone_id.write_snapshot(writer);
+            }
+            EntityEvent::Dismount(joint_id) => {
+                writer.write_u8(2);
+                joint_id.write_snapshot(writer);
+            }
+            EntityEvent::SledBreak(joint_id) => {
+                writer.write_u8(3);
+                joint_id.write_snapshot(writer);
+            }
+        }
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        match reader.read_u8()? {
+            0 => Ok(EntityEvent::Contact {
+                point_id: EntityPointId::read_snapshot(reader)?,
+                line_id: LineId::read_snapshot(reader)?,
+                iteration: reader.read_u8()?,
+            }),
+            1 => Ok(EntityEvent::BoneBreak(EntityBoneId::read_snapshot(reader)?)),
+            2 => Ok(EntityEvent::Dismount(EntityJointId::read_snapshot(reader)?)),
+            3 => Ok(EntityEvent::SledBreak(EntityJointId::read_snapshot(
+                reader,
+            )?)),
+            value => Err(Error::InvalidSnapshot(format!(
+                "Unknown entity event: {}",
+                value
+            ))),
+        }
+    }
+}
@@ -1,17 +1,27 @@
+mod bone_state;
 mod point_state;
 mod skeleton_state;
 
-pub(crate) use point_state::EntityPointState;
+pub use bone_state::EntityBoneState;
+pub use point_state::EntityPointState;
 pub(crate) use skeleton_state::EntitySkeletonState;
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, iter::zip};
 
 use geometry::Point;
+use lr_physics_grid::CellRange;
 use vector2d::Vector2Df;
 
 use crate::{
-    entity_registry::{EntityPointId, EntityTemplate, MountPhase, RemountVersion},
+    Error, PhysicsMoment, browser_math,
+    byte_sink::ByteSink as _,
+    entity_registry::{
+        EntityBoneId, EntityEvent, EntityJointId, EntityPointId, EntityTemplate, MountPhase,
+        RemountVersion,
+    },
     line_registry::LineRegistry,
+    snapshot::{SnapshotReader, SnapshotWriter},
+    stable_hash::StableHasher,
 };
 
 const MAX_ITERATION: u8 = 6;
@@ -21,6 +31,8 @@ pub struct EntityState {
     skeleton_state: EntitySkeletonState,
     // Cloning a BTreeMap is 5x slower than cloning a Vec, but at this scale it's a difference of nanoseconds
     point_states: BTreeMap<EntityPointId, EntityPointState>,
+    // Events of the frame that produced this state, if the engine's event log is on
+    events: Option<Vec<EntityEvent>>,
 }
 
 impl EntityState {
@@ -28,14 +40,27 @@ impl EntityState {
         template: &EntityTemplate,
         initial_offset: Vector2Df,
         initial_velocity: Vector2Df,
+        initial_rotation: f64,
+        event_log: bool,
     ) -> Self {
         let skeleton_state = EntitySkeletonState::new(MountPhase::Mounted, true);
         let mut point_states = BTreeMap::new();
+        // Same trig as linerider.com in the template's browser, so rotated riders start identically
+        let radians = browser_math::degrees_to_radians(initial_rotation);
+        let js_engine = template.js_engine();
+        let (sin, cos) = (js_engine.sin(radians), js_engine.cos(radians));
 
         for (point_id, point_template) in template.points() {
-            let position = point_template
-                .initial_position()
-                .translated_by(initial_offset);
+            let initial_position = point_template.initial_position();
+            let rotated_position = if initial_rotation == 0.0 {
+                initial_position
+            } else {
+                Point::new(
+                    initial_position.x() * cos - initial_position.y() * sin,
+                    initial_position.x() * sin + initial_position.y() * cos,
+                )
+            };
+            let position = rotated_position.translated_by(initial_offset);
             let velocity = initial_velocity;
             let point_state =
                 EntityPointState::new(position, velocity, position.translated_by(-velocity));
@@ -45,6 +70,7 @@ impl EntityState {
         Self {
             skeleton_state,
             point_states,
+            events: event_log.then(Vec::new),
         }
     }
 
@@ -62,6 +88,11 @@ impl EntityState {
             .collect()
     }
 
+    /// Point states keyed by id, see `EntityTemplate::point_id` for finding points by name
+    pub fn point_states(&self) -> &BTreeMap<EntityPointId, EntityPointState> {
+        &self.point_states
+    }
+
     pub fn mount_phase(&self) -> MountPhase {
         self.skeleton_state.mount_phase()
     }
@@ -70,6 +101,56 @@ impl EntityState {
         self.skeleton_state.sled_intact()
     }
 
+    /// Contacts, bone breaks, and dismounts in the order they happened during the frame that
+    /// produced this state, empty unless the engine's event log is on
+    pub fn events(&self) -> &[EntityEvent] {
+        self.events.as_deref().unwrap_or_default()
+    }
+
+    /// Approximate memory used by this state, ignoring map bookkeeping
+    pub(crate) fn estimated_size(&self) -> usize {
+        size_of::<Self>()
+            + self.point_states.len() * (size_of::<EntityPointId>() + size_of::<EntityPointState>())
+            + size_of_val(self.events())
+    }
+
+    /// Length and intactness of each bone, given the entity's rest lengths in bone id order
+    ///
+    /// Breakable bones stay broken from the moment the entity dismounts until it remounts
+    pub(super) fn bone_states(
+        &self,
+        template: &EntityTemplate,
+        bone_rest_lengths: &[f64],
+    ) -> BTreeMap<EntityBoneId, EntityBoneState> {
+        let mount_phase = self.mount_phase();
+        let remounting = mount_phase.is_remounting();
+        let mounted = mount_phase.is_mounted() || remounting;
+        zip(template.bones(), bone_rest_lengths)
+            .map(|((bone_id, bone), rest_length)| {
+                let point_states = (
+                    self.point_state(&bone.point_ids().0),
+                    self.point_state(&bone.point_ids().1),
+                );
+                let length = point_states
+                    .0
+                    .position()
+                    .distance_from(point_states.1.position());
+                let intact = !bone.is_breakable()
+                    || (mounted && bone.get_intact(point_states, remounting, *rest_length));
+                (*bone_id, EntityBoneState::new(length, *rest_length, intact))
+            })
+            .collect()
+    }
+
+    /// Signed angle in radians of each joint, from its first bone to its second
+    pub(super) fn joint_angles(&self, template: &EntityTemplate) -> BTreeMap<EntityJointId, f64> {
+        template
+            .joints()
+            .iter()
+            .map(|(joint_id, joint)| (*joint_id, joint.angle(self, template)))
+            .collect()
+    }
+
     pub(crate) fn skeleton_state(&self) -> &EntitySkeletonState {
         &self.skeleton_state
     }
@@ -90,19 +171,121 @@ impl EntityState {
             .expect("Point state should exist when retrieved internally")
     }
 
+    /// Stable hash of the exact bit patterns of every point state, the mount phase, and the
+    /// sled, which stays the same across runs and platforms
+    ///
+    /// Logged events aren't hashed, so the hash doesn't depend on the event log setting
+    pub fn hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.hash_into(&mut hasher);
+        hasher.finish()
+    }
+
+    pub(crate) fn hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_usize(self.point_states.len());
+        for (point_id, point_state) in &self.point_states {
+            point_id.hash_into(hasher);
+            hasher.write_point(point_state.position());
+            hasher.write_vector(point_state.velocity());
+            hasher.write_point(point_state.computed_previous_position());
+        }
+        self.mount_phase().hash_into(hasher);
+        hasher.write_bool(self.sled_intact());
+    }
+
+    pub(super) fn write_snapshot(&self, writer: &mut SnapshotWriter) -> Result<(), Error> {
+        writer.write_len(self.point_states.len())?;
+        for (point_id, point_state) in &self.point_states {
+            point_id.write_snapshot(writer);
+            writer.write_point(point_state.position());
+            writer.write_vector(point_state.velocity());
+            writer.write_point(point_state.computed_previous_position());
+        }
+        self.mount_phase().write_snapshot(writer);
+        writer.write_bool(self.sled_intact());
+        writer
+            .write_option(self.events.as_deref(), |writer, events| {
+                writer.write_len(events.len())?;
+                for event in events {
+                    event.write_snapshot(writer);
+                }
+                Ok(())
+            })
+            .transpose()?;
+        Ok(())
+    }
+
+    /// Reads a state written by `write_snapshot`, which must have the same points as `template`
+    pub(super) fn read_snapshot(
+        reader: &mut SnapshotReader,
+        template: &EntityTemplate,
+    ) -> Result<Self, Error> {
+        let mut point_states = BTreeMap::new();
+        for _ in 0..reader.read_len()? {
+            let point_id = EntityPointId::read_snapshot(reader)?;
+            let position = reader.read_point()?;
+            let velocity = reader.read_vector()?;
+            let computed_previous_position = reader.read_point()?;
+            point_states.insert(
+                point_id,
+                EntityPointState::new(position, velocity, computed_previous_position),
+            );
+        }
+
+        if !point_states.keys().eq(template.points().keys()) {
+            return Err(Error::InvalidSnapshot(
+                "Entity state doesn't match its template's points".to_string(),
+            ));
+        }
+
+        let mount_phase = MountPhase::read_snapshot(reader)?;
+        let sled_intact = reader.read_bool()?;
+        let events = reader.read_option(|reader| {
+            (0..reader.read_len()?)
+                .map(|_| EntityEvent::read_snapshot(reader))
+                .collect()
+        })?;
+
+        Ok(Self {
+            skeleton_state: EntitySkeletonState::new(mount_phase, sled_intact),
+            point_states,
+            events,
+        })
+    }
+
+    /// Records events from the next simulated frame onward
+    pub(super) fn enable_event_log(&mut self) {
+        if self.events.is_none() {
+            self.events = Some(Vec::new());
+        }
+    }
+
+    fn record_event(&mut self, event: EntityEvent) {
+        if let Some(events) = &mut self.events {
+            events.push(event);
+        }
+    }
+
     // This is the main physics loop that transforms an entity state
+    // Stops early if a moment other than None is given
+    // Expands line_query_cells to include every grid cell lines were queried from
     // Returns whether the rider dismounted
     pub(super) fn process_frame(
         &mut self,
         template: &EntityTemplate,
+        bone_rest_lengths: &[f64],
         line_registry: &LineRegistry,
+        gravity: Vector2Df,
+        moment: PhysicsMoment,
+        line_query_cells: &mut Option<CellRange>,
     ) -> bool {
         let mut dismounted = false;
 
-        for (point_id, point) in template.points() {
-            const GRAVITY_MULTIPLIER: f64 = 0.175;
-            let gravity = Vector2Df::down() * GRAVITY_MULTIPLIER;
+        if let Some(events) = &mut self.events {
+            events.clear();
+        }
 
+        for (point_id, point) in template.points() {
             let point_state = self.point_state_mut(point_id);
             let computed_velocity = point_state
                 .position()
@@ -117,10 +300,14 @@ impl EntityState {
             );
         }
 
+        if moment == PhysicsMoment::Momentum {
+            return dismounted;
+        }
+
         let initial_mount_phase = self.mount_phase();
 
-        for _ in 0..MAX_ITERATION {
-            for bone in template.bones().values() {
+        for iteration in 0..MAX_ITERATION {
+            for ((bone_id, bone), rest_length) in zip(template.bones(), bone_rest_lengths) {
                 if !bone.is_flutter() {
                     let point_states = (
                         self.point_state(&bone.point_ids().0),
@@ -133,7 +320,11 @@ impl EntityState {
                     };
 
                     if !bone.is_breakable() {
-                        let adjusted = bone.get_adjusted(point_states, mount_phase.is_remounting());
+                        let adjusted = bone.get_adjusted(
+                            point_states,
+                            mount_phase.is_remounting(),
+                            *rest_length,
+                        );
                         self.point_state_mut(&bone.point_ids().0).update(
                             Some(adjusted.0),
                             None,
@@ -147,9 +338,13 @@ impl EntityState {
                     } else if (mount_phase.is_remounting() || mount_phase.is_mounted())
                         && !dismounted
                     {
-                        if bone.get_intact(point_states, mount_phase.is_remounting()) {
-                            let adjusted =
-                                bone.get_adjusted(point_states, mount_phase.is_remounting());
+                        if bone.get_intact(point_states, mount_phase.is_remounting(), *rest_length)
+                        {
+                            let adjusted = bone.get_adjusted(
+                                point_states,
+                                mount_phase.is_remounting(),
+                                *rest_length,
+                            );
                             self.point_state_mut(&bone.point_ids().0).update(
                                 Some(adjusted.0),
                                 None,
@@ -162,6 +357,7 @@ impl EntityState {
                             );
                         } else {
                             dismounted = true;
+                            self.record_event(EntityEvent::BoneBreak(*bone_id));
 
                             let next_mount_phase = match template.remount_version() {
                                 RemountVersion::None => MountPhase::Dismounted {
@@ -192,30 +388,51 @@ impl EntityState {
 
             for (point_id, point) in template.points() {
                 if point.is_contact() {
-                    let point_state = self.point_state_mut(point_id);
-                    for line in line_registry.lines_near_point(point_state.position()) {
-                        if let Some((new_position, new_computed_previous_position)) =
-                            line.check_interaction(point, point_state)
+                    let position = self.point_state(point_id).position();
+                    let query_cells = line_registry.cells_near_point(position);
+                    *line_query_cells = Some(match line_query_cells {
+                        Some(cells) => cells.expanded_to_include(&query_cells),
+                        None => query_cells,
+                    });
+                    for (line_id, line) in line_registry.lines_near_point(position) {
+                        if let Some((new_position, new_computed_previous_position)) = line
+                            .check_interaction(
+                                point,
+                                self.point_state(point_id),
+                                line_registry.frictionless(),
+                            )
                         {
-                            point_state.update(
+                            self.point_state_mut(point_id).update(
                                 Some(new_position),
                                 None,
                                 Some(new_computed_previous_position),
                             );
+                            self.record_event(EntityEvent::Contact {
+                                point_id: *point_id,
+                                line_id,
+                                iteration,
+                            });
                         }
                     }
                 }
             }
+
+            if let PhysicsMoment::Iteration(target_iteration) = moment
+                && (target_iteration <= iteration || iteration == MAX_ITERATION - 1)
+            {
+                return dismounted;
+            }
         }
 
-        for bone in template.bones().values() {
+        for (bone, rest_length) in zip(template.bones().values(), bone_rest_lengths) {
             if bone.is_flutter() {
                 let point_states = (
                     self.point_state(&bone.point_ids().0),
                     self.point_state(&bone.point_ids().1),
                 );
                 let mount_phase = self.skeleton_state().mount_phase();
-                let adjusted = bone.get_adjusted(point_states, mount_phase.is_remounting());
+                let adjusted =
+                    bone.get_adjusted(point_states, mount_phase.is_remounting(), *rest_length);
                 self.point_state_mut(&bone.point_ids().0)
                     .update(Some(adjusted.0), None, None);
                 self.point_state_mut(&bone.point_ids().1)
@@ -223,12 +440,17 @@ impl EntityState {
             }
         }
 
+        if moment == PhysicsMoment::Flutter {
+            return dismounted;
+        }
+
         let mount_phase = self.skeleton_state().mount_phase();
 
         if mount_phase.is_mounted() || mount_phase.is_remounting() {
-            for joint in template.joints().values() {
+            for (joint_id, joint) in template.joints() {
                 if joint.should_break(self, template) && !dismounted {
                     dismounted = true;
+                    self.record_event(EntityEvent::Dismount(*joint_id));
 
                     let next_mount_phase = match template.remount_version() {
                         RemountVersion::None => MountPhase::Dismounted {
@@ -264,9 +486,10 @@ impl EntityState {
             || template.remount_version().is_none()
             || template.remount_version().is_comv2()
         {
-            for joint in template.joints().values() {
+            for (joint_id, joint) in template.joints() {
                 if !joint.is_mount() && joint.should_break(self, template) && sled_intact {
                     self.skeleton_state_mut().set_sled_intact(false);
+                    self.record_event(EntityEvent::SledBreak(*joint_id));
                 }
             }
         }
@@ -275,10 +498,19 @@ impl EntityState {
     }
 
     // This retrieves the next mount phase
-    pub(super) fn process_mount_phase(
+    // Other states are paired with the mapping from this template's sled points to theirs, and
+    // the remount version of their template
+    pub(super) fn process_mount_phase<'a>(
         &mut self,
         template: &EntityTemplate,
-        other_states: &mut Vec<EntityState>,
+        bone_rest_lengths: &[f64],
+        other_states: impl IntoIterator<
+            Item = (
+                &'a mut EntityState,
+                &'a [(EntityPointId, EntityPointId)],
+                RemountVersion,
+            ),
+        >,
     ) {
         let current_mount_phase = self.skeleton_state().mount_phase();
         let sled_intact = self.skeleton_state().sled_intact();
@@ -310,8 +542,14 @@ impl EntityState {
                         } => {
                             let mut can_swap = false;
 
-                            for other_state in other_states {
-                                if self.can_swap_sleds(template, other_state) {
+                            for (other_state, sled_mapping, other_remount_version) in other_states {
+                                if self.can_swap_sleds(
+                                    template,
+                                    bone_rest_lengths,
+                                    other_state,
+                                    sled_mapping,
+                                    other_remount_version,
+                                ) {
                                     can_swap = true;
                                     break;
                                 }
@@ -337,7 +575,7 @@ impl EntityState {
                         MountPhase::Remounting {
                             frames_until_mounted,
                         } => {
-                            if self.skeleton_can_enter_phase(template, false) {
+                            if self.skeleton_can_enter_phase(template, bone_rest_lengths, false) {
                                 if frames_until_mounted == 0 {
                                     MountPhase::Mounted
                                 } else {
@@ -376,8 +614,14 @@ impl EntityState {
                 } => {
                     let mut can_swap = false;
 
-                    for other_state in other_states {
-                        if self.can_swap_sleds(template, other_state) {
+                    for (other_state, sled_mapping, other_remount_version) in other_states {
+                        if self.can_swap_sleds(
+                            template,
+                            bone_rest_lengths,
+                            other_state,
+                            sled_mapping,
+                            other_remount_version,
+                        ) {
                             can_swap = true;
                             break;
                         }
@@ -402,11 +646,12 @@ impl EntityState {
                 MountPhase::Remounting {
                     frames_until_mounted,
                 } => {
-                    let next_timer = if self.skeleton_can_enter_phase(template, false) {
-                        frames_until_mounted.saturating_sub(1)
-                    } else {
-                        template.mounted_timer()
-                    };
+                    let next_timer =
+                        if self.skeleton_can_enter_phase(template, bone_rest_lengths, false) {
+                            frames_until_mounted.saturating_sub(1)
+                        } else {
+                            template.mounted_timer()
+                        };
 
                     if next_timer == 0 {
                         MountPhase::Mounted
@@ -427,42 +672,53 @@ impl EntityState {
     pub(super) fn can_swap_sleds(
         &mut self,
         template: &EntityTemplate,
+        bone_rest_lengths: &[f64],
         other_state: &mut EntityState,
+        sled_mapping: &[(EntityPointId, EntityPointId)],
+        other_remount_version: RemountVersion,
     ) -> bool {
         if other_state.sled_intact() && other_state.mount_phase().is_dismounted() {
             // Swap sleds to check entity can safely remount
-            self.swap_skeleton_sleds(template, other_state);
+            self.swap_skeleton_sleds(template, other_state, sled_mapping, other_remount_version);
 
-            if self.skeleton_can_enter_phase(template, true) {
+            if self.skeleton_can_enter_phase(template, bone_rest_lengths, true) {
                 return true;
             }
 
             // Swap sleds back if we failed
-            self.swap_skeleton_sleds(template, other_state);
+            self.swap_skeleton_sleds(template, other_state, sled_mapping, other_remount_version);
         }
 
         false
     }
 
-    fn swap_skeleton_sleds(&mut self, template: &EntityTemplate, other_state: &mut EntityState) {
-        match template.remount_version() {
-            RemountVersion::ComV2 | RemountVersion::LRA => {
-                let sled_intact = self.skeleton_state().sled_intact();
-                let other_sled_intact = other_state.skeleton_state().sled_intact();
-                other_state
-                    .skeleton_state_mut()
-                    .set_sled_intact(sled_intact);
-                self.skeleton_state_mut().set_sled_intact(other_sled_intact);
-            }
-            _ => {}
+    fn swap_skeleton_sleds(
+        &mut self,
+        template: &EntityTemplate,
+        other_state: &mut EntityState,
+        sled_mapping: &[(EntityPointId, EntityPointId)],
+        other_remount_version: RemountVersion,
+    ) {
+        // Whether a sled stays intact only travels with the sled if both versions track it that
+        // way, so mixed riders swap the same way whichever of them remounts
+        let tracks_sled_intact =
+            |version| matches!(version, RemountVersion::ComV2 | RemountVersion::LRA);
+        if tracks_sled_intact(template.remount_version())
+            && tracks_sled_intact(other_remount_version)
+        {
+            let sled_intact = self.skeleton_state().sled_intact();
+            let other_sled_intact = other_state.skeleton_state().sled_intact();
+            other_state
+                .skeleton_state_mut()
+                .set_sled_intact(sled_intact);
+            self.skeleton_state_mut().set_sled_intact(other_sled_intact);
         }
 
-        // Assumes sled points are in same order, because they originate from same template
-        for point_id in template.sled_points() {
+        for (point_id, other_point_id) in sled_mapping {
             let point_state = self.point_state(point_id).clone();
-            let other_point_state = other_state.point_state(point_id).clone();
+            let other_point_state = other_state.point_state(other_point_id).clone();
 
-            other_state.point_state_mut(point_id).update(
+            other_state.point_state_mut(other_point_id).update(
                 Some(point_state.position()),
                 Some(point_state.velocity()),
                 Some(point_state.computed_previous_position()),
@@ -478,15 +734,18 @@ impl EntityState {
     pub(super) fn skeleton_can_enter_phase(
         &self,
         template: &EntityTemplate,
+        bone_rest_lengths: &[f64],
         target_phase_is_remounting: bool,
     ) -> bool {
-        for bone in template.bones().values() {
+        for (bone, rest_length) in zip(template.bones().values(), bone_rest_lengths) {
             let point_states = (
                 self.point_state(&bone.point_ids().0),
                 self.point_state(&bone.point_ids().1),
             );
 
-            if bone.is_breakable() && !bone.get_intact(point_states, target_phase_is_remounting) {
+            if bone.is_breakable()
+                && !bone.get_intact(point_states, target_phase_is_remounting, *rest_length)
+            {
                 return false;
             }
         }
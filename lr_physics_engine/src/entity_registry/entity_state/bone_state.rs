@@ -0,0 +1,38 @@
+/// Snapshot of a bone's length within an entity state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityBoneState {
+    length: f64,
+    rest_length: f64,
+    intact: bool,
+}
+
+impl EntityBoneState {
+    pub(super) fn new(length: f64, rest_length: f64, intact: bool) -> Self {
+        EntityBoneState {
+            length,
+            rest_length,
+            intact,
+        }
+    }
+
+    /// Distance between the bone's points
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+
+    /// Length the bone adjusts its points towards, which can differ per entity
+    pub fn rest_length(&self) -> f64 {
+        self.rest_length
+    }
+
+    /// Current length as a fraction of the rest length, above 1 when stretched
+    pub fn stretch_ratio(&self) -> f64 {
+        self.length / self.rest_length
+    }
+
+    /// Whether the bone is within its endurance, always true for unbreakable bones and false for
+    /// breakable bones while the entity is dismounted
+    pub fn intact(&self) -> bool {
+        self.intact
+    }
+}
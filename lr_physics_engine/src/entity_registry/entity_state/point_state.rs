@@ -1,8 +1,9 @@
 use geometry::Point;
 use vector2d::Vector2Df;
 
+/// Position and momentum of a point within an entity state
 #[derive(Debug, Clone)]
-pub(crate) struct EntityPointState {
+pub struct EntityPointState {
     position: Point,
     velocity: Vector2Df,
     computed_previous_position: Point,
@@ -33,15 +34,17 @@ impl EntityPointState {
             computed_previous_position.unwrap_or(self.computed_previous_position);
     }
 
-    pub(crate) fn position(&self) -> Point {
+    pub fn position(&self) -> Point {
         self.position
     }
 
-    pub(crate) fn velocity(&self) -> Vector2Df {
+    pub fn velocity(&self) -> Vector2Df {
         self.velocity
     }
 
-    pub(crate) fn computed_previous_position(&self) -> Point {
+    /// Position the point's momentum is computed from on the next frame, which friction and
+    /// acceleration lines shift away from its actual previous position
+    pub fn computed_previous_position(&self) -> Point {
         self.computed_previous_position
     }
 }
@@ -1,9 +1,21 @@
 use geometry::Point;
-use std::collections::BTreeMap;
-
-use crate::entity_registry::{
-    EntityBone, EntityBoneBuilder, EntityJoint, EntityJointBuilder, EntityPoint,
-    EntityPointBuilder, RemountVersion,
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
+use vector2d::Vector2Df;
+
+use crate::{
+    Error,
+    browser_math::JsEngine,
+    byte_sink::ByteSink as _,
+    entity_registry::{
+        EntityBone, EntityBoneBuilder, EntityJoint, EntityJointBuilder, EntityPoint,
+        EntityPointBuilder, RemountVersion,
+        skeleton_graph::{self, SkeletonGraph},
+    },
+    snapshot::{SnapshotReader, SnapshotWriter},
+    stable_hash::StableHasher,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -15,6 +27,58 @@ pub struct EntityBoneId(usize);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EntityJointId(usize);
 
+impl EntityPointId {
+    pub(crate) fn hash_into(self, hasher: &mut StableHasher) {
+        hasher.write_usize(self.0);
+    }
+
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.0);
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityPointId(reader.read_usize()?))
+    }
+}
+
+impl EntityBoneId {
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.0);
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityBoneId(reader.read_usize()?))
+    }
+}
+
+impl EntityJointId {
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.0);
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityJointId(reader.read_usize()?))
+    }
+}
+
+impl fmt::Display for EntityPointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for EntityBoneId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for EntityJointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 pub struct EntityTemplate {
     points: BTreeMap<EntityPointId, EntityPoint>,
     bones: BTreeMap<EntityBoneId, EntityBone>,
@@ -23,6 +87,10 @@ pub struct EntityTemplate {
     remounting_timer: u32,
     mounted_timer: u32,
     remount_version: RemountVersion,
+    gravity: Option<Vector2Df>,
+    rest_lengths_after_offset: bool,
+    js_engine: JsEngine,
+    sled_points: Vec<EntityPointId>,
 }
 
 impl EntityTemplate {
@@ -54,12 +122,114 @@ impl EntityTemplate {
         self.remount_version
     }
 
-    // TODO use computed graph for this
-    pub(crate) fn sled_points(&self) -> Vec<&EntityPointId> {
-        let x: Vec<&EntityPointId> = self.points.keys().collect();
-        x.get(0..=3)
-            .expect("Default riders should always have 4 points")
-            .to_vec()
+    /// Gravity override for entities of this template, otherwise the engine's gravity is used
+    pub(crate) fn gravity(&self) -> Option<Vector2Df> {
+        self.gravity
+    }
+
+    /// Browser engine whose trig rotates entities of this template by their initial rotation
+    pub(crate) fn js_engine(&self) -> JsEngine {
+        self.js_engine
+    }
+
+    /// Looks up a point by the name given to its builder
+    pub fn point_id(&self, name: &str) -> Option<EntityPointId> {
+        self.points
+            .iter()
+            .find(|(_, point)| point.name() == Some(name))
+            .map(|(point_id, _)| *point_id)
+    }
+
+    pub fn point_name(&self, point_id: EntityPointId) -> Option<&str> {
+        self.points.get(&point_id).and_then(EntityPoint::name)
+    }
+
+    /// Rest length of each bone in id order, for an entity starting at `offset`
+    pub(crate) fn bone_rest_lengths(&self, offset: Vector2Df) -> Vec<f64> {
+        self.bones
+            .values()
+            .map(|bone| {
+                if !self.rest_lengths_after_offset {
+                    return bone.rest_length();
+                }
+
+                let (point0, point1) = bone.point_ids();
+                match (self.points.get(&point0), self.points.get(&point1)) {
+                    (Some(point0), Some(point1)) => {
+                        bone.rest_length_at_offset((point0, point1), offset)
+                    }
+                    _ => bone.rest_length(),
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn write_snapshot(&self, writer: &mut SnapshotWriter) -> Result<(), Error> {
+        writer.write_len(self.points.len())?;
+        for point in self.points.values() {
+            point.write_snapshot(writer)?;
+        }
+        writer.write_len(self.bones.len())?;
+        for bone in self.bones.values() {
+            bone.write_snapshot(writer);
+        }
+        writer.write_len(self.joints.len())?;
+        for joint in self.joints.values() {
+            joint.write_snapshot(writer);
+        }
+        writer.write_u32(self.dismounted_timer);
+        writer.write_u32(self.remounting_timer);
+        writer.write_u32(self.mounted_timer);
+        self.remount_version.write_snapshot(writer);
+        writer.write_option(self.gravity, SnapshotWriter::write_vector);
+        writer.write_bool(self.rest_lengths_after_offset);
+        self.js_engine.write_snapshot(writer);
+        Ok(())
+    }
+
+    /// Rebuilds a template written by `write_snapshot`, validating it like any other template
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        let mut builder = EntityTemplateBuilder::new();
+        for _ in 0..reader.read_len()? {
+            builder.add_point(EntityPointBuilder::read_snapshot(reader)?);
+        }
+        for _ in 0..reader.read_len()? {
+            builder.add_bone(EntityBoneBuilder::read_snapshot(reader)?);
+        }
+        for _ in 0..reader.read_len()? {
+            builder.add_joint(EntityJointBuilder::read_snapshot(reader)?);
+        }
+        builder.dismounted_timer = reader.read_u32()?;
+        builder.remounting_timer = reader.read_u32()?;
+        builder.mounted_timer = reader.read_u32()?;
+        builder.remount_version = RemountVersion::read_snapshot(reader)?;
+        builder.gravity = reader.read_option(SnapshotReader::read_vector)?;
+        builder.rest_lengths_after_offset = reader.read_bool()?;
+        builder.js_engine = JsEngine::read_snapshot(reader)?;
+        builder.build()
+    }
+
+    /// Points of the largest rigid part of the skeleton, swapped between entities when remounting
+    pub fn sled_points(&self) -> &[EntityPointId] {
+        &self.sled_points
+    }
+
+    /// Joints connecting separate rigid parts, which dismount instead of breaking the sled
+    pub fn mount_joints(&self) -> Vec<EntityJointId> {
+        self.joints
+            .iter()
+            .filter(|(_, joint)| joint.is_mount())
+            .map(|(joint_id, _)| *joint_id)
+            .collect()
+    }
+
+    /// Pairs each sled point with the matching sled point of `other`, if the sleds have the same
+    /// shape, so riders from either template can swap sleds when remounting
+    pub fn sled_point_mapping(
+        &self,
+        other: &EntityTemplate,
+    ) -> Option<Vec<(EntityPointId, EntityPointId)>> {
+        skeleton_graph::sled_mapping(self, other)
     }
 }
 
@@ -71,6 +241,9 @@ pub struct EntityTemplateBuilder {
     remounting_timer: u32,
     mounted_timer: u32,
     remount_version: RemountVersion,
+    gravity: Option<Vector2Df>,
+    rest_lengths_after_offset: bool,
+    js_engine: JsEngine,
 }
 
 impl Default for EntityTemplateBuilder {
@@ -89,22 +262,28 @@ impl EntityTemplateBuilder {
             remounting_timer: 0,
             mounted_timer: 0,
             remount_version: RemountVersion::None,
+            gravity: None,
+            rest_lengths_after_offset: false,
+            js_engine: JsEngine::default(),
         }
     }
 
-    pub(crate) fn add_point(&mut self, point: EntityPointBuilder) -> EntityPointId {
+    /// Points are processed in the order they're added
+    pub fn add_point(&mut self, point: EntityPointBuilder) -> EntityPointId {
         let id = EntityPointId(self.points.len());
         self.points.insert(id, point);
         id
     }
 
-    pub(crate) fn add_bone(&mut self, bone: EntityBoneBuilder) -> EntityBoneId {
+    /// Bones are processed in the order they're added, and must connect points of this template
+    pub fn add_bone(&mut self, bone: EntityBoneBuilder) -> EntityBoneId {
         let id = EntityBoneId(self.bones.len());
         self.bones.insert(id, bone);
         id
     }
 
-    pub(crate) fn add_joint(&mut self, joint: EntityJointBuilder) -> EntityJointId {
+    /// Joints must connect bones of this template
+    pub fn add_joint(&mut self, joint: EntityJointBuilder) -> EntityJointId {
         let id = EntityJointId(self.joints.len());
         self.joints.insert(id, joint);
         id
@@ -130,24 +309,67 @@ impl EntityTemplateBuilder {
         self
     }
 
-    pub fn build(self) -> EntityTemplate {
-        let points = self
+    pub fn gravity(mut self, gravity: Vector2Df) -> Self {
+        self.gravity = Some(gravity);
+        self
+    }
+
+    /// Computes bone rest lengths per entity after applying its start offset, like LRA and OpenLR
+    pub fn rest_lengths_after_offset(mut self, rest_lengths_after_offset: bool) -> Self {
+        self.rest_lengths_after_offset = rest_lengths_after_offset;
+        self
+    }
+
+    /// Rotates entities by their initial rotation with the trig of this browser engine, since
+    /// linerider.com start angles land on slightly different positions in Chrome and Firefox
+    pub fn js_engine(mut self, js_engine: JsEngine) -> Self {
+        self.js_engine = js_engine;
+        self
+    }
+
+    /// Fails if a bone or joint references a point or bone missing from this template,
+    /// or if two points share a name
+    pub fn build(self) -> Result<EntityTemplate, Error> {
+        let points: BTreeMap<EntityPointId, EntityPoint> = self
             .points
             .into_iter()
             .map(|x| (x.0, x.1.build()))
             .collect();
-        let bones = self
-            .bones
-            .into_iter()
-            .map(|x| (x.0, x.1.build(&points)))
-            .collect();
-        let joints = self
-            .joints
-            .into_iter()
-            .map(|x| (x.0, x.1.build()))
-            .collect();
 
-        EntityTemplate {
+        let mut names = HashSet::new();
+        for name in points.values().filter_map(EntityPoint::name) {
+            if !names.insert(name) {
+                return Err(Error::DuplicatePointName(name.to_string()));
+            }
+        }
+
+        let mut bones = BTreeMap::new();
+        for (bone_id, bone) in self.bones {
+            let point_ids = bone.point_ids();
+            let resolve_point = |point_id| {
+                points
+                    .get(&point_id)
+                    .ok_or(Error::BonePointNotFound(bone_id, point_id))
+            };
+            let bone_points = (resolve_point(point_ids.0)?, resolve_point(point_ids.1)?);
+            bones.insert(bone_id, bone.build(bone_points));
+        }
+
+        let skeleton_graph = SkeletonGraph::new(points.keys().copied(), &bones);
+
+        let mut joints = BTreeMap::new();
+        for (joint_id, joint) in self.joints {
+            let bone_ids = joint.bones();
+            for bone_id in [bone_ids.0, bone_ids.1] {
+                if !bones.contains_key(&bone_id) {
+                    return Err(Error::JointBoneNotFound(joint_id, bone_id));
+                }
+            }
+            let is_mount = skeleton_graph.is_mount_joint(bone_ids, &bones);
+            joints.insert(joint_id, joint.build(is_mount));
+        }
+
+        Ok(EntityTemplate {
             points,
             bones,
             joints,
@@ -155,14 +377,13 @@ impl EntityTemplateBuilder {
             remounting_timer: self.remounting_timer,
             mounted_timer: self.mounted_timer,
             remount_version: self.remount_version,
-        }
+            gravity: self.gravity,
+            rest_lengths_after_offset: self.rest_lengths_after_offset,
+            js_engine: self.js_engine,
+            sled_points: skeleton_graph.sled_points().to_vec(),
+        })
     }
 
-    // Known bug: Default riders of different remount versions are not able to
-    // cross-remount with each other because they come from different templates,
-    // even though they normally would in linerider.com. This is such a niche case
-    // that it's probably not worth fixing.
-    // TODO Maybe we could solve this with computing graph isomorphism?
     /// Builds the original bosh skeleton
     pub fn default_rider(version: RemountVersion) -> EntityTemplate {
         let repel_length_factor = 0.5;
@@ -181,7 +402,9 @@ impl EntityTemplateBuilder {
         };
 
         // Remount version also affects physics processing order, which is why it's needed internally
-        let mut skeleton = Self::new().remount_version(version);
+        let mut skeleton = Self::new()
+            .remount_version(version)
+            .rest_lengths_after_offset(version.is_lra());
 
         skeleton = match version {
             RemountVersion::None => skeleton,
@@ -193,57 +416,93 @@ impl EntityTemplateBuilder {
 
         let peg = skeleton.add_point(
             EntityPointBuilder::new(Point::new(0.0, 0.0))
+                .name("peg".to_string())
                 .is_contact(true)
                 .contact_friction(0.8),
         );
-        let tail =
-            skeleton.add_point(EntityPointBuilder::new(Point::new(0.0, 5.0)).is_contact(true));
-        let nose =
-            skeleton.add_point(EntityPointBuilder::new(Point::new(15.0, 5.0)).is_contact(true));
-        let string =
-            skeleton.add_point(EntityPointBuilder::new(Point::new(17.5, 0.0)).is_contact(true));
+        let tail = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(0.0, 5.0))
+                .name("tail".to_string())
+                .is_contact(true),
+        );
+        let nose = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(15.0, 5.0))
+                .name("nose".to_string())
+                .is_contact(true),
+        );
+        let string = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(17.5, 0.0))
+                .name("string".to_string())
+                .is_contact(true),
+        );
         let butt = skeleton.add_point(
             EntityPointBuilder::new(Point::new(5.0, 0.0))
+                .name("butt".to_string())
                 .is_contact(true)
                 .contact_friction(0.8),
         );
         let shoulder = skeleton.add_point(
             EntityPointBuilder::new(Point::new(5.0, -5.5))
+                .name("shoulder".to_string())
                 .is_contact(true)
                 .contact_friction(0.8),
         );
         let right_hand = skeleton.add_point(
             EntityPointBuilder::new(Point::new(11.5, -5.0))
+                .name("right_hand".to_string())
                 .is_contact(true)
                 .contact_friction(0.1),
         );
         let left_hand = skeleton.add_point(
             EntityPointBuilder::new(Point::new(11.5, -5.0))
+                .name("left_hand".to_string())
                 .is_contact(true)
                 .contact_friction(0.1),
         );
-        let left_foot =
-            skeleton.add_point(EntityPointBuilder::new(Point::new(10.0, 5.0)).is_contact(true));
-        let right_foot =
-            skeleton.add_point(EntityPointBuilder::new(Point::new(10.0, 5.0)).is_contact(true));
-        let scarf0 = skeleton
-            .add_point(EntityPointBuilder::new(Point::new(3.0, -5.5)).air_friction(scarf_friction));
-        let scarf1 = skeleton
-            .add_point(EntityPointBuilder::new(Point::new(1.0, -5.5)).air_friction(scarf_friction));
+        let left_foot = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(10.0, 5.0))
+                .name("left_foot".to_string())
+                .is_contact(true),
+        );
+        let right_foot = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(10.0, 5.0))
+                .name("right_foot".to_string())
+                .is_contact(true),
+        );
+        let scarf0 = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(3.0, -5.5))
+                .name("scarf0".to_string())
+                .air_friction(scarf_friction),
+        );
+        let scarf1 = skeleton.add_point(
+            EntityPointBuilder::new(Point::new(1.0, -5.5))
+                .name("scarf1".to_string())
+                .air_friction(scarf_friction),
+        );
         let scarf2 = skeleton.add_point(
-            EntityPointBuilder::new(Point::new(-1.0, -5.5)).air_friction(scarf_friction),
+            EntityPointBuilder::new(Point::new(-1.0, -5.5))
+                .name("scarf2".to_string())
+                .air_friction(scarf_friction),
         );
         let scarf3 = skeleton.add_point(
-            EntityPointBuilder::new(Point::new(-3.0, -5.5)).air_friction(scarf_friction),
+            EntityPointBuilder::new(Point::new(-3.0, -5.5))
+                .name("scarf3".to_string())
+                .air_friction(scarf_friction),
         );
         let scarf4 = skeleton.add_point(
-            EntityPointBuilder::new(Point::new(-5.0, -5.5)).air_friction(scarf_friction),
+            EntityPointBuilder::new(Point::new(-5.0, -5.5))
+                .name("scarf4".to_string())
+                .air_friction(scarf_friction),
         );
         let scarf5 = skeleton.add_point(
-            EntityPointBuilder::new(Point::new(-7.0, -5.5)).air_friction(scarf_friction),
+            EntityPointBuilder::new(Point::new(-7.0, -5.5))
+                .name("scarf5".to_string())
+                .air_friction(scarf_friction),
         );
         let scarf6 = skeleton.add_point(
-            EntityPointBuilder::new(Point::new(-9.0, -5.5)).air_friction(scarf_friction),
+            EntityPointBuilder::new(Point::new(-9.0, -5.5))
+                .name("scarf6".to_string())
+                .air_friction(scarf_friction),
         );
 
         let sled_back = skeleton.add_bone(
@@ -363,9 +622,11 @@ impl EntityTemplateBuilder {
         skeleton.add_bone(EntityBoneBuilder::new((scarf5, scarf6)).bias(1.0));
 
         skeleton.add_joint(EntityJointBuilder::new(sled_back, sled_front));
-        skeleton.add_joint(EntityJointBuilder::new(torso, sled_front).is_mount(true));
+        skeleton.add_joint(EntityJointBuilder::new(torso, sled_front));
 
-        skeleton.build()
+        skeleton
+            .build()
+            .expect("Default rider should only reference its own points and bones")
     }
 }
 
@@ -391,6 +652,9 @@ impl From<EntityTemplate> for EntityTemplateBuilder {
             remounting_timer: skeleton.remounting_timer,
             mounted_timer: skeleton.mounted_timer,
             remount_version: skeleton.remount_version,
+            gravity: skeleton.gravity,
+            rest_lengths_after_offset: skeleton.rest_lengths_after_offset,
+            js_engine: skeleton.js_engine,
         }
     }
 }
@@ -1,6 +1,10 @@
 use vector2d::Vector2Df;
 
-use crate::entity_registry::{EntityBoneId, EntityState, EntityTemplate};
+use crate::{
+    Error,
+    entity_registry::{EntityBoneId, EntityState, EntityTemplate},
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
 
 /// Computed properties when built
 struct Computed {
@@ -22,7 +26,28 @@ impl EntityJoint {
         self.computed.is_mount
     }
 
+    pub(crate) fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        self.bones.0.write_snapshot(writer);
+        self.bones.1.write_snapshot(writer);
+    }
+
     pub(crate) fn should_break(&self, state: &EntityState, template: &EntityTemplate) -> bool {
+        let bone_vectors = self.bone_vectors(state, template);
+        Vector2Df::cross(bone_vectors.0, bone_vectors.1) < 0.0
+    }
+
+    /// Signed angle in radians from the first bone to the second, negative past the break point
+    pub(crate) fn angle(&self, state: &EntityState, template: &EntityTemplate) -> f64 {
+        let bone_vectors = self.bone_vectors(state, template);
+        Vector2Df::cross(bone_vectors.0, bone_vectors.1)
+            .atan2(Vector2Df::dot(bone_vectors.0, bone_vectors.1))
+    }
+
+    fn bone_vectors(
+        &self,
+        state: &EntityState,
+        template: &EntityTemplate,
+    ) -> (Vector2Df, Vector2Df) {
         let bones = (
             template
                 .bones()
@@ -37,49 +62,47 @@ impl EntityJoint {
         let bone0_p1 = state.point_state(&bones.0.point_ids().1);
         let bone1_p0 = state.point_state(&bones.1.point_ids().0);
         let bone1_p1 = state.point_state(&bones.1.point_ids().1);
-        let bone_vectors = (
+        (
             bone0_p0.position().vector_from(bone0_p1.position()),
             bone1_p0.position().vector_from(bone1_p1.position()),
-        );
-        Vector2Df::cross(bone_vectors.0, bone_vectors.1) < 0.0
+        )
     }
 }
 
 /// Joint builder for custom skeletons
 pub struct EntityJointBuilder {
     bones: (EntityBoneId, EntityBoneId),
-    is_mount: bool,
 }
 
 impl EntityJointBuilder {
     pub fn new(b1: EntityBoneId, b2: EntityBoneId) -> EntityJointBuilder {
-        Self {
-            bones: (b1, b2),
-            is_mount: false,
-        }
+        Self { bones: (b1, b2) }
+    }
+
+    pub(crate) fn bones(&self) -> (EntityBoneId, EntityBoneId) {
+        self.bones
     }
 
-    // TODO remove this by using computed graph
-    pub fn is_mount(mut self, is_mount: bool) -> Self {
-        self.is_mount = is_mount;
-        self
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityJointBuilder {
+            bones: (
+                EntityBoneId::read_snapshot(reader)?,
+                EntityBoneId::read_snapshot(reader)?,
+            ),
+        })
     }
 
-    pub fn build(self) -> EntityJoint {
+    /// Builds with whether the template's skeleton graph found this to be a mount joint
+    pub(crate) fn build(self, is_mount: bool) -> EntityJoint {
         EntityJoint {
             bones: self.bones,
-            computed: Computed {
-                is_mount: self.is_mount,
-            },
+            computed: Computed { is_mount },
         }
     }
 }
 
 impl From<EntityJoint> for EntityJointBuilder {
     fn from(joint: EntityJoint) -> Self {
-        Self {
-            bones: joint.bones,
-            is_mount: joint.computed.is_mount,
-        }
+        Self { bones: joint.bones }
     }
 }
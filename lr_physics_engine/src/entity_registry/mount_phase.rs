@@ -1,4 +1,11 @@
-#[derive(Debug, Clone, Copy)]
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    snapshot::{SnapshotReader, SnapshotWriter},
+    stable_hash::StableHasher,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MountPhase {
     Mounted,
     Dismounting { frames_until_dismounted: u32 },
@@ -22,4 +29,53 @@ impl MountPhase {
     pub fn is_remounting(&self) -> bool {
         matches!(self, MountPhase::Remounting { .. })
     }
+
+    // Tags are part of saved snapshots and hashes, so existing ones must never change
+    fn tag_and_timer(self) -> (u8, u32) {
+        match self {
+            MountPhase::Mounted => (0, 0),
+            MountPhase::Dismounting {
+                frames_until_dismounted,
+            } => (1, frames_until_dismounted),
+            MountPhase::Dismounted {
+                frames_until_remounting,
+            } => (2, frames_until_remounting),
+            MountPhase::Remounting {
+                frames_until_mounted,
+            } => (3, frames_until_mounted),
+        }
+    }
+
+    pub(crate) fn hash_into(self, hasher: &mut StableHasher) {
+        let (tag, timer) = self.tag_and_timer();
+        hasher.write_bytes(&[tag]);
+        hasher.write_u32(timer);
+    }
+
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        let (tag, timer) = self.tag_and_timer();
+        writer.write_u8(tag);
+        writer.write_u32(timer);
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        let tag = reader.read_u8()?;
+        let timer = reader.read_u32()?;
+        match tag {
+            0 => Ok(MountPhase::Mounted),
+            1 => Ok(MountPhase::Dismounting {
+                frames_until_dismounted: timer,
+            }),
+            2 => Ok(MountPhase::Dismounted {
+                frames_until_remounting: timer,
+            }),
+            3 => Ok(MountPhase::Remounting {
+                frames_until_mounted: timer,
+            }),
+            value => Err(Error::InvalidSnapshot(format!(
+                "Unknown mount phase: {}",
+                value
+            ))),
+        }
+    }
 }
@@ -1,7 +1,14 @@
 use geometry::Point;
 
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
+
 /// Constructed point that holds props after building
 pub struct EntityPoint {
+    name: Option<String>,
     initial_position: Point,
     is_contact: bool,
     contact_friction: f64,
@@ -9,6 +16,10 @@ pub struct EntityPoint {
 }
 
 impl EntityPoint {
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     pub(crate) fn initial_position(&self) -> Point {
         self.initial_position
     }
@@ -24,19 +35,32 @@ impl EntityPoint {
     pub(crate) fn air_friction(&self) -> f64 {
         self.air_friction
     }
+
+    pub(crate) fn write_snapshot(&self, writer: &mut SnapshotWriter) -> Result<(), Error> {
+        writer
+            .write_option(self.name.as_deref(), SnapshotWriter::write_str)
+            .transpose()?;
+        writer.write_point(self.initial_position);
+        writer.write_bool(self.is_contact);
+        writer.write_f64(self.contact_friction);
+        writer.write_f64(self.air_friction);
+        Ok(())
+    }
 }
 
+/// Point builder for custom skeletons
 pub struct EntityPointBuilder {
+    name: Option<String>,
     initial_position: Point,
     is_contact: bool,
     contact_friction: f64,
     air_friction: f64,
 }
 
-/// Point builder for custom skeletons
 impl EntityPointBuilder {
-    pub(crate) fn new(initial_position: Point) -> EntityPointBuilder {
+    pub fn new(initial_position: Point) -> EntityPointBuilder {
         Self {
+            name: None,
             initial_position,
             is_contact: false,
             contact_friction: 0.0,
@@ -44,6 +68,12 @@ impl EntityPointBuilder {
         }
     }
 
+    /// Name for looking up this point's id, unique within its template
+    pub fn name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
     pub fn is_contact(mut self, is_contact: bool) -> Self {
         self.is_contact = is_contact;
         self
@@ -59,8 +89,19 @@ impl EntityPointBuilder {
         self
     }
 
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(EntityPointBuilder {
+            name: reader.read_option(SnapshotReader::read_string)?,
+            initial_position: reader.read_point()?,
+            is_contact: reader.read_bool()?,
+            contact_friction: reader.read_f64()?,
+            air_friction: reader.read_f64()?,
+        })
+    }
+
     pub(crate) fn build(self) -> EntityPoint {
         EntityPoint {
+            name: self.name,
             initial_position: self.initial_position,
             is_contact: self.is_contact,
             contact_friction: self.contact_friction,
@@ -72,6 +113,7 @@ impl EntityPointBuilder {
 impl From<EntityPoint> for EntityPointBuilder {
     fn from(point: EntityPoint) -> Self {
         EntityPointBuilder {
+            name: point.name,
             initial_position: point.initial_position,
             is_contact: point.is_contact,
             contact_friction: point.contact_friction,
@@ -1,3 +1,9 @@
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    snapshot::{SnapshotReader, SnapshotWriter},
+};
+
 #[derive(Debug, Clone, Copy)]
 pub enum RemountVersion {
     None,
@@ -6,6 +12,18 @@ pub enum RemountVersion {
     LRA,
 }
 
+#[cfg(feature = "lr_format_core")]
+impl From<lr_format_core::RemountVersion> for RemountVersion {
+    fn from(remount_version: lr_format_core::RemountVersion) -> Self {
+        match remount_version {
+            lr_format_core::RemountVersion::None => RemountVersion::None,
+            lr_format_core::RemountVersion::ComV1 => RemountVersion::ComV1,
+            lr_format_core::RemountVersion::ComV2 => RemountVersion::ComV2,
+            lr_format_core::RemountVersion::LRA => RemountVersion::LRA,
+        }
+    }
+}
+
 impl RemountVersion {
     pub fn is_none(&self) -> bool {
         matches!(self, RemountVersion::None)
@@ -22,4 +40,26 @@ impl RemountVersion {
     pub fn is_lra(&self) -> bool {
         matches!(self, RemountVersion::LRA)
     }
+
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_u8(match self {
+            RemountVersion::None => 0,
+            RemountVersion::ComV1 => 1,
+            RemountVersion::ComV2 => 2,
+            RemountVersion::LRA => 3,
+        });
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        match reader.read_u8()? {
+            0 => Ok(RemountVersion::None),
+            1 => Ok(RemountVersion::ComV1),
+            2 => Ok(RemountVersion::ComV2),
+            3 => Ok(RemountVersion::LRA),
+            value => Err(Error::InvalidSnapshot(format!(
+                "Unknown remount version: {}",
+                value
+            ))),
+        }
+    }
 }
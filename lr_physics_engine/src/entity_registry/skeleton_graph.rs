@@ -0,0 +1,221 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter::zip,
+};
+
+use crate::entity_registry::{EntityBone, EntityBoneId, EntityPointId, EntityTemplate};
+
+/// Groups of points held together by unbreakable bones, ignoring repel-only bones
+pub(crate) struct SkeletonGraph {
+    components: BTreeMap<EntityPointId, usize>,
+    sled_points: Vec<EntityPointId>,
+}
+
+impl SkeletonGraph {
+    pub(crate) fn new(
+        point_ids: impl Iterator<Item = EntityPointId>,
+        bones: &BTreeMap<EntityBoneId, EntityBone>,
+    ) -> Self {
+        let mut components: BTreeMap<EntityPointId, usize> = point_ids
+            .enumerate()
+            .map(|(component, point_id)| (point_id, component))
+            .collect();
+
+        let rigid_bones: Vec<&EntityBone> = bones
+            .values()
+            .filter(|bone| !bone.is_breakable() && !bone.is_repel())
+            .collect();
+
+        // Small skeletons, so merging by relabeling is fast enough
+        for bone in &rigid_bones {
+            let (point0, point1) = bone.point_ids();
+            let (Some(&component0), Some(&component1)) =
+                (components.get(&point0), components.get(&point1))
+            else {
+                continue;
+            };
+
+            if component0 != component1 {
+                for component in components.values_mut() {
+                    if *component == component1 {
+                        *component = component0;
+                    }
+                }
+            }
+        }
+
+        let mut component_points: BTreeMap<usize, Vec<EntityPointId>> = BTreeMap::new();
+        for (point_id, component) in &components {
+            component_points
+                .entry(*component)
+                .or_default()
+                .push(*point_id);
+        }
+
+        let mut component_edges: BTreeMap<usize, BTreeSet<(EntityPointId, EntityPointId)>> =
+            BTreeMap::new();
+        for bone in &rigid_bones {
+            let (point0, point1) = bone.point_ids();
+            if point0 == point1 {
+                continue;
+            }
+            if let Some(component) = components.get(&point0) {
+                component_edges
+                    .entry(*component)
+                    .or_default()
+                    .insert((point0.min(point1), point0.max(point1)));
+            }
+        }
+
+        // The sled is the largest component braced enough to keep its shape (2n - 3 distinct
+        // bones for n points), which rules out tree-like bodies and scarves
+        let sled_points = component_points
+            .iter()
+            .filter(|(component, points)| {
+                let edge_count = component_edges.get(component).map_or(0, BTreeSet::len);
+                points.len() >= 2 && edge_count + 3 >= 2 * points.len()
+            })
+            .max_by_key(|(_, points)| points.len())
+            .map(|(_, points)| points.clone())
+            .unwrap_or_default();
+
+        SkeletonGraph {
+            components,
+            sled_points,
+        }
+    }
+
+    pub(crate) fn sled_points(&self) -> &[EntityPointId] {
+        &self.sled_points
+    }
+
+    /// Mount joints connect bones of separate components, like the rider's torso to the sled
+    pub(crate) fn is_mount_joint(
+        &self,
+        (bone0, bone1): (EntityBoneId, EntityBoneId),
+        bones: &BTreeMap<EntityBoneId, EntityBone>,
+    ) -> bool {
+        let mut components = [bone0, bone1]
+            .into_iter()
+            .filter_map(|bone_id| bones.get(&bone_id))
+            .flat_map(|bone| [bone.point_ids().0, bone.point_ids().1])
+            .filter_map(|point_id| self.components.get(&point_id));
+
+        let Some(first_component) = components.next() else {
+            return false;
+        };
+
+        components.any(|component| component != first_component)
+    }
+}
+
+/// Maps each sled point of `template` to the matching sled point of `other_template`, if both sleds
+/// have the same shape: matching point properties and matching rest lengths between points
+pub(crate) fn sled_mapping(
+    template: &EntityTemplate,
+    other_template: &EntityTemplate,
+) -> Option<Vec<(EntityPointId, EntityPointId)>> {
+    if template.sled_points().len() != other_template.sled_points().len() {
+        return None;
+    }
+
+    let sled_bones = sled_bone_lengths(template);
+    let other_sled_bones = sled_bone_lengths(other_template);
+    if sled_bones.len() != other_sled_bones.len() {
+        return None;
+    }
+
+    let mut mapped_points = Vec::new();
+    if map_sled_points(
+        (template, &sled_bones),
+        (other_template, &other_sled_bones),
+        &mut mapped_points,
+    ) {
+        Some(zip(template.sled_points().iter().copied(), mapped_points).collect())
+    } else {
+        None
+    }
+}
+
+fn sled_bone_lengths(template: &EntityTemplate) -> BTreeMap<(EntityPointId, EntityPointId), f64> {
+    let sled_points = template.sled_points();
+    template
+        .bones()
+        .values()
+        .filter(|bone| !bone.is_breakable() && !bone.is_repel())
+        .map(|bone| bone.point_ids())
+        .filter(|(point0, point1)| {
+            point0 != point1 && sled_points.contains(point0) && sled_points.contains(point1)
+        })
+        .map(|(point0, point1)| {
+            let length = template
+                .points()
+                .get(&point0)
+                .zip(template.points().get(&point1))
+                .map_or(0.0, |(point0, point1)| {
+                    point0
+                        .initial_position()
+                        .distance_from(point1.initial_position())
+                });
+            ((point0.min(point1), point0.max(point1)), length)
+        })
+        .collect()
+}
+
+// Backtracking search, trying the same point order first
+#[expect(clippy::float_cmp)]
+fn map_sled_points(
+    (template, sled_bones): (
+        &EntityTemplate,
+        &BTreeMap<(EntityPointId, EntityPointId), f64>,
+    ),
+    (other_template, other_sled_bones): (
+        &EntityTemplate,
+        &BTreeMap<(EntityPointId, EntityPointId), f64>,
+    ),
+    mapped_points: &mut Vec<EntityPointId>,
+) -> bool {
+    let sled_points = template.sled_points();
+    let Some(point_id) = sled_points.get(mapped_points.len()) else {
+        return true;
+    };
+    let Some(point) = template.points().get(point_id) else {
+        return false;
+    };
+
+    for candidate_id in other_template.sled_points() {
+        if mapped_points.contains(candidate_id) {
+            continue;
+        }
+        let Some(candidate) = other_template.points().get(candidate_id) else {
+            continue;
+        };
+
+        let same_point = point.is_contact() == candidate.is_contact()
+            && point.contact_friction() == candidate.contact_friction()
+            && point.air_friction() == candidate.air_friction();
+        let same_bones =
+            zip(sled_points, mapped_points.iter()).all(|(previous, other_previous)| {
+                let length = sled_bones.get(&(*point_id.min(previous), *point_id.max(previous)));
+                let other_length = other_sled_bones.get(&(
+                    *candidate_id.min(other_previous),
+                    *candidate_id.max(other_previous),
+                ));
+                length == other_length
+            });
+
+        if same_point && same_bones {
+            mapped_points.push(*candidate_id);
+            if map_sled_points(
+                (template, sled_bones),
+                (other_template, other_sled_bones),
+                mapped_points,
+            ) {
+                return true;
+            }
+            mapped_points.pop();
+        }
+    }
+
+    false
+}
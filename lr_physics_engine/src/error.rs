@@ -0,0 +1,71 @@
+use std::{error, fmt};
+
+use crate::{
+    entity_registry::{EntityBoneId, EntityId, EntityJointId, EntityPointId},
+    line_registry::LineId,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    EntityNotFound(EntityId),
+    LineNotFound(LineId),
+    BonePointNotFound(EntityBoneId, EntityPointId),
+    JointBoneNotFound(EntityJointId, EntityBoneId),
+    DuplicatePointName(String),
+    InvalidSnapshot(String),
+    UnsupportedSnapshotVersion(u8),
+    SnapshotLineMismatch,
+    SnapshotTriggerMismatch,
+    SnapshotSettingMismatch(String),
+    SnapshotLengthOverflow(usize),
+    EntityCountMismatch(usize, usize),
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EntityNotFound(id) => {
+                write!(f, "Entity with id not found: {}", id)
+            }
+            Error::LineNotFound(id) => {
+                write!(f, "Line with id not found: {}", id)
+            }
+            Error::BonePointNotFound(bone_id, point_id) => {
+                write!(f, "Bone {} references missing point: {}", bone_id, point_id)
+            }
+            Error::JointBoneNotFound(joint_id, bone_id) => {
+                write!(f, "Joint {} references missing bone: {}", joint_id, bone_id)
+            }
+            Error::DuplicatePointName(name) => {
+                write!(f, "Multiple points share name: {}", name)
+            }
+            Error::InvalidSnapshot(reason) => {
+                write!(f, "Invalid snapshot: {}", reason)
+            }
+            Error::UnsupportedSnapshotVersion(version) => {
+                write!(f, "Unsupported snapshot version: {}", version)
+            }
+            Error::SnapshotLineMismatch => {
+                write!(f, "Snapshot was exported with different lines")
+            }
+            Error::SnapshotTriggerMismatch => {
+                write!(f, "Snapshot was exported with different triggers")
+            }
+            Error::SnapshotSettingMismatch(setting) => {
+                write!(f, "Snapshot was exported with a different {}", setting)
+            }
+            Error::SnapshotLengthOverflow(len) => {
+                write!(f, "Snapshot collection is too long: {}", len)
+            }
+            Error::EntityCountMismatch(first_count, second_count) => {
+                write!(
+                    f,
+                    "Engines have different entity counts: {} and {}",
+                    first_count, second_count
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::entity_registry::EntityState;
+
+/// Controls which simulated frames are kept in memory
+///
+/// Frames that aren't kept are resimulated from the closest earlier kept frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CachePolicy {
+    keyframe_interval: u32,
+    recent_frames: u32,
+    memory_budget: Option<usize>,
+}
+
+impl Default for CachePolicy {
+    /// Keeps every frame without a memory budget
+    fn default() -> Self {
+        CachePolicy {
+            keyframe_interval: 1,
+            recent_frames: 0,
+            memory_budget: None,
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Keeps every `keyframe_interval`th frame plus the `recent_frames` most recently simulated frames
+    ///
+    /// If `memory_budget` is given in bytes, recent frames are dropped first when it's exceeded,
+    /// then keyframes are thinned out by doubling the interval between them
+    pub fn new(keyframe_interval: u32, recent_frames: u32, memory_budget: Option<usize>) -> Self {
+        CachePolicy {
+            keyframe_interval: keyframe_interval.max(1),
+            recent_frames,
+            memory_budget,
+        }
+    }
+
+    pub fn keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+
+    pub fn recent_frames(&self) -> u32 {
+        self.recent_frames
+    }
+
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.memory_budget
+    }
+}
+
+/// Snapshot of how much the frame cache currently holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheUsage {
+    cached_frames: usize,
+    estimated_bytes: usize,
+}
+
+impl CacheUsage {
+    pub fn cached_frames(&self) -> usize {
+        self.cached_frames
+    }
+
+    /// Approximate heap and inline size of the cached entity states
+    pub fn estimated_bytes(&self) -> usize {
+        self.estimated_bytes
+    }
+}
+
+pub(crate) struct FrameCache {
+    policy: CachePolicy,
+    // Doubles whenever keyframes alone exceed the memory budget
+    keyframe_interval: u32,
+    keyframes: BTreeMap<u32, Vec<EntityState>>,
+    recent_frames: BTreeMap<u32, Vec<EntityState>>,
+    // Insertion order of recent frames, oldest first
+    recent_order: VecDeque<u32>,
+    keyframe_bytes: usize,
+    recent_bytes: usize,
+}
+
+impl FrameCache {
+    pub(crate) fn new(policy: CachePolicy) -> Self {
+        FrameCache {
+            policy,
+            keyframe_interval: policy.keyframe_interval(),
+            keyframes: BTreeMap::new(),
+            recent_frames: BTreeMap::new(),
+            recent_order: VecDeque::new(),
+            keyframe_bytes: 0,
+            recent_bytes: 0,
+        }
+    }
+
+    pub(crate) fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: CachePolicy) {
+        self.policy = policy;
+        self.clear();
+    }
+
+    pub(crate) fn usage(&self) -> CacheUsage {
+        CacheUsage {
+            cached_frames: self.keyframes.len() + self.recent_frames.len(),
+            estimated_bytes: self.keyframe_bytes + self.recent_bytes,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keyframe_interval = self.policy.keyframe_interval();
+        self.keyframes.clear();
+        self.recent_frames.clear();
+        self.recent_order.clear();
+        self.keyframe_bytes = 0;
+        self.recent_bytes = 0;
+    }
+
+    /// Removes every frame after `frame`
+    pub(crate) fn truncate(&mut self, frame: u32) {
+        let Some(first_removed) = frame.checked_add(1) else {
+            return;
+        };
+
+        for states in self.keyframes.split_off(&first_removed).values() {
+            self.keyframe_bytes -= frame_size(states);
+        }
+
+        for states in self.recent_frames.split_off(&first_removed).values() {
+            self.recent_bytes -= frame_size(states);
+        }
+
+        self.recent_order
+            .retain(|recent_frame| *recent_frame < first_removed);
+    }
+
+    /// The latest cached frame at or before `frame`
+    pub(crate) fn closest_at_or_before(&self, frame: u32) -> Option<(u32, &Vec<EntityState>)> {
+        let keyframe = self.keyframes.range(..=frame).next_back();
+        let recent_frame = self.recent_frames.range(..=frame).next_back();
+
+        match (keyframe, recent_frame) {
+            (Some(keyframe), Some(recent_frame)) => Some(if keyframe.0 >= recent_frame.0 {
+                (*keyframe.0, keyframe.1)
+            } else {
+                (*recent_frame.0, recent_frame.1)
+            }),
+            (Some((frame, states)), None) | (None, Some((frame, states))) => Some((*frame, states)),
+            (None, None) => None,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, frame: u32, states: Vec<EntityState>) {
+        let size = frame_size(&states);
+
+        if frame.is_multiple_of(self.keyframe_interval) {
+            if let Some(old_states) = self.keyframes.insert(frame, states) {
+                self.keyframe_bytes -= frame_size(&old_states);
+            }
+            self.keyframe_bytes += size;
+        } else if self.policy.recent_frames() > 0 {
+            if let Some(old_states) = self.recent_frames.insert(frame, states) {
+                self.recent_bytes -= frame_size(&old_states);
+                self.recent_order
+                    .retain(|recent_frame| *recent_frame != frame);
+            }
+            self.recent_bytes += size;
+            self.recent_order.push_back(frame);
+
+            while self.recent_order.len() > self.policy.recent_frames() as usize {
+                self.remove_oldest_recent_frame();
+            }
+        } else {
+            // Frame isn't kept by this policy
+        }
+
+        self.enforce_memory_budget();
+    }
+
+    fn remove_oldest_recent_frame(&mut self) {
+        if let Some(frame) = self.recent_order.pop_front()
+            && let Some(states) = self.recent_frames.remove(&frame)
+        {
+            self.recent_bytes -= frame_size(&states);
+        }
+    }
+
+    fn enforce_memory_budget(&mut self) {
+        let Some(memory_budget) = self.policy.memory_budget() else {
+            return;
+        };
+
+        while self.keyframe_bytes + self.recent_bytes > memory_budget
+            && !self.recent_order.is_empty()
+        {
+            self.remove_oldest_recent_frame();
+        }
+
+        while self.keyframe_bytes > memory_budget && self.keyframes.len() > 1 {
+            let Some(keyframe_interval) = self.keyframe_interval.checked_mul(2) else {
+                break;
+            };
+            self.keyframe_interval = keyframe_interval;
+
+            let mut removed_bytes = 0;
+            self.keyframes.retain(|frame, states| {
+                let keep = frame.is_multiple_of(keyframe_interval);
+                if !keep {
+                    removed_bytes += frame_size(states);
+                }
+                keep
+            });
+            self.keyframe_bytes -= removed_bytes;
+        }
+    }
+}
+
+fn frame_size(states: &[EntityState]) -> usize {
+    states.iter().map(EntityState::estimated_size).sum()
+}
@@ -1,7 +1,18 @@
+pub mod browser_math;
+mod byte_sink;
+mod divergence;
 mod engine;
 pub mod entity_registry;
+mod error;
+mod frame_cache;
 pub mod line_registry;
 mod moment;
+mod snapshot;
+mod stable_hash;
+pub mod trigger_registry;
 
+pub use divergence::{Divergence, find_divergence};
 pub use engine::PhysicsEngine;
+pub use error::Error;
+pub use frame_cache::{CachePolicy, CacheUsage};
 pub use moment::PhysicsMoment;
@@ -2,16 +2,40 @@ mod physics_line;
 
 pub use physics_line::{PhysicsLine, PhysicsLineBuilder};
 
-use geometry::Point;
-use lr_physics_grid::{Grid, GridLineId, GridVersion};
-use std::collections::HashMap;
+use geometry::{Line, Point};
+use lr_physics_grid::{CellRange, Grid, GridLineId, GridVersion};
+use std::{collections::HashMap, fmt};
 
-#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+use crate::{
+    Error,
+    byte_sink::ByteSink as _,
+    snapshot::{SnapshotReader, SnapshotWriter},
+    stable_hash::StableHasher,
+};
+
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
 pub struct LineId(GridLineId);
 
+impl LineId {
+    pub(crate) fn write_snapshot(self, writer: &mut SnapshotWriter) {
+        writer.write_u32(self.0.to_raw());
+    }
+
+    pub(crate) fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, Error> {
+        Ok(LineId(GridLineId::from_raw(reader.read_u32()?)))
+    }
+}
+
+impl fmt::Display for LineId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 pub(crate) struct LineRegistry {
     grid: Grid,
     line_lookup: HashMap<LineId, PhysicsLine>,
+    frictionless: bool,
 }
 
 impl LineRegistry {
@@ -19,13 +43,27 @@ impl LineRegistry {
         LineRegistry {
             grid: Grid::new(grid_version),
             line_lookup: HashMap::new(),
+            frictionless: false,
         }
     }
 
+    pub(crate) fn grid_version(&self) -> GridVersion {
+        self.grid.version()
+    }
+
     pub(crate) fn set_grid_version(&mut self, new_version: GridVersion) {
         self.grid.set_version(new_version);
     }
 
+    /// Whether lines ignore the contact friction of points colliding with them
+    pub(crate) fn frictionless(&self) -> bool {
+        self.frictionless
+    }
+
+    pub(crate) fn set_frictionless(&mut self, frictionless: bool) {
+        self.frictionless = frictionless;
+    }
+
     pub(crate) fn add_line(&mut self, line: PhysicsLine) -> LineId {
         let id = LineId(self.grid.add_line(line.endpoints()));
         self.line_lookup.insert(id, line);
@@ -37,27 +75,61 @@ impl LineRegistry {
     }
 
     /// Replaces a line with a new line, preserving its id
-    pub(crate) fn replace_line(&mut self, id: LineId, new_line: PhysicsLine) {
+    pub(crate) fn replace_line(&mut self, id: LineId, new_line: PhysicsLine) -> Result<(), Error> {
+        let line = self
+            .line_lookup
+            .get_mut(&id)
+            .ok_or(Error::LineNotFound(id))?;
         self.grid.update_line(id.0, new_line.endpoints());
-        self.line_lookup.insert(id, new_line);
+        *line = new_line;
+        Ok(())
+    }
+
+    pub(crate) fn remove_line(&mut self, id: LineId) -> Result<(), Error> {
+        self.line_lookup
+            .remove(&id)
+            .ok_or(Error::LineNotFound(id))?;
+        self.grid.remove_line(id.0);
+        Ok(())
     }
 
-    pub(crate) fn remove_line(&mut self, id: LineId) {
-        let line = self.line_lookup.remove(&id);
-        if line.is_some() {
-            self.grid.remove_line(id.0);
+    /// Hashes the id and physics props of every line, independent of grid version and friction
+    pub(crate) fn hash(&self) -> u64 {
+        let mut line_ids: Vec<&LineId> = self.line_lookup.keys().collect();
+        line_ids.sort_unstable_by_key(|id| id.0);
+
+        let mut hasher = StableHasher::new();
+        for line_id in line_ids {
+            hasher.write_u32(line_id.0.to_raw());
+            if let Some(line) = self.line_lookup.get(line_id) {
+                line.hash_into(&mut hasher);
+            }
         }
+        hasher.finish()
+    }
+
+    /// Range of grid cells `lines_near_point` reads around a point
+    pub(crate) fn cells_near_point(&self, point: Point) -> CellRange {
+        self.grid.get_cells_near_point(point)
+    }
+
+    /// Range of grid cells a line registers in, whether or not it was added
+    pub(crate) fn cells_along(&self, endpoints: Line) -> CellRange {
+        self.grid.get_cells_along(&endpoints)
     }
 
     /// Uses the grid to collect all lines around a point
-    pub(crate) fn lines_near_point(&self, point: Point) -> Vec<&PhysicsLine> {
+    pub(crate) fn lines_near_point(&self, point: Point) -> Vec<(LineId, &PhysicsLine)> {
         let line_ids = self.grid.get_lines_near_point(point);
         line_ids
             .iter()
             .map(|id| {
-                self.line_lookup
-                    .get(&LineId(*id))
-                    .expect("Line in grid should also exist in line lookup")
+                let line_id = LineId(*id);
+                let line = self
+                    .line_lookup
+                    .get(&line_id)
+                    .expect("Line in grid should also exist in line lookup");
+                (line_id, line)
             })
             .collect()
     }
@@ -1,7 +1,11 @@
 use geometry::{Line, Point};
 use vector2d::Vector2Df;
 
-use crate::entity_registry::{EntityPoint, EntityPointState};
+use crate::{
+    byte_sink::ByteSink as _,
+    entity_registry::{EntityPoint, EntityPointState},
+    stable_hash::StableHasher,
+};
 
 const MAX_EXTENSION_SIZE: f64 = 0.25;
 const ACCELERATION_FACTOR: f64 = 0.1;
@@ -47,10 +51,21 @@ impl PhysicsLine {
         self.acceleration_multiplier
     }
 
+    pub(crate) fn hash_into(&self, hasher: &mut StableHasher) {
+        hasher.write_point(self.endpoints.p0());
+        hasher.write_point(self.endpoints.p1());
+        hasher.write_bool(self.flipped);
+        hasher.write_bool(self.left_extension);
+        hasher.write_bool(self.right_extension);
+        hasher.write_f64(self.height);
+        hasher.write_f64(self.acceleration_multiplier);
+    }
+
     pub(crate) fn check_interaction(
         &self,
         point: &EntityPoint,
         point_state: &EntityPointState,
+        frictionless: bool,
     ) -> Option<(Point, Point)> {
         let offset_from_point = point_state.position().vector_from(self.endpoints.p0());
         let moving_into_line = Vector2Df::dot(self.normal_unit, point_state.velocity()) > 0.0;
@@ -68,8 +83,14 @@ impl PhysicsLine {
                 .position()
                 .translated_by(-self.normal_unit * distance_from_line_top);
 
+            let contact_friction = if frictionless {
+                0.0
+            } else {
+                point.contact_friction()
+            };
+
             let mut friction_vector =
-                (self.normal_unit.rotated_cw() * point.contact_friction()) * distance_from_line_top;
+                (self.normal_unit.rotated_cw() * contact_friction) * distance_from_line_top;
 
             if point_state.computed_previous_position().x() >= new_position.x() {
                 friction_vector = friction_vector.flipped_horizontal();
@@ -200,3 +221,17 @@ impl From<PhysicsLine> for PhysicsLineBuilder {
         }
     }
 }
+
+#[cfg(feature = "lr_format_core")]
+impl From<&lr_format_core::StandardLine> for PhysicsLineBuilder {
+    fn from(line: &lr_format_core::StandardLine) -> Self {
+        PhysicsLineBuilder {
+            endpoints: line.endpoints(),
+            flipped: line.flipped(),
+            left_extension: line.left_extension(),
+            right_extension: line.right_extension(),
+            height: line.height(),
+            acceleration_multiplier: line.multiplier(),
+        }
+    }
+}
@@ -1,4 +1,14 @@
-#[derive(Clone, Copy, Debug)]
+/// A point within a frame's physics step, similar to LRA's iterations and subiterations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PhysicsMoment {
+    /// The end of the frame, after mount phases have been updated
     None,
+    /// After points have been moved by their momentum and gravity
+    Momentum,
+    /// After a bone constraint and line collision pass, counting from 0
+    ///
+    /// Iterations past the last one are treated as the last one
+    Iteration(u8),
+    /// After flutter bones have been adjusted, before joints are checked
+    Flutter,
 }
@@ -0,0 +1,177 @@
+use geometry::Point;
+use lr_physics_grid::CellRange;
+use vector2d::{Vector2Df, Vector2Di};
+
+use crate::{Error, byte_sink::ByteSink};
+
+pub(crate) const MAGIC_NUMBER: &[u8; 4] = b"LRPE";
+pub(crate) const VERSION: u8 = 1;
+
+/// Appends little endian values to a snapshot
+pub(crate) struct SnapshotWriter {
+    bytes: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn new() -> Self {
+        SnapshotWriter { bytes: Vec::new() }
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Lengths are written ahead of collections, so they must fit in a u32
+    pub(crate) fn write_len(&mut self, len: usize) -> Result<(), Error> {
+        let len = u32::try_from(len).map_err(|_| Error::SnapshotLengthOverflow(len))?;
+        self.write_u32(len);
+        Ok(())
+    }
+
+    pub(crate) fn write_cell_range(&mut self, cell_range: &CellRange) {
+        self.write_i32(cell_range.min().x());
+        self.write_i32(cell_range.min().y());
+        self.write_i32(cell_range.max().x());
+        self.write_i32(cell_range.max().y());
+    }
+
+    pub(crate) fn write_str(&mut self, value: &str) -> Result<(), Error> {
+        self.write_len(value.len())?;
+        self.write_bytes(value.as_bytes());
+        Ok(())
+    }
+
+    /// Returns what `write` returned, if the value was written
+    pub(crate) fn write_option<T, R>(
+        &mut self,
+        value: Option<T>,
+        write: impl FnOnce(&mut Self, T) -> R,
+    ) -> Option<R> {
+        self.write_bool(value.is_some());
+        value.map(|value| write(self, value))
+    }
+}
+
+impl ByteSink for SnapshotWriter {
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+}
+
+/// Reads little endian values from a snapshot, failing on truncated or malformed data
+pub(crate) struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        SnapshotReader { bytes }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(crate) fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let (array, rest) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or_else(|| Error::InvalidSnapshot("Unexpected end of data".to_string()))?;
+        self.bytes = rest;
+        Ok(*array)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(u8::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool, Error> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(Error::InvalidSnapshot(format!("Invalid bool: {}", value))),
+        }
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_usize(&mut self) -> Result<usize, Error> {
+        let value = self.read_u64()?;
+        usize::try_from(value)
+            .map_err(|_| Error::InvalidSnapshot(format!("Index out of range: {}", value)))
+    }
+
+    pub(crate) fn read_len(&mut self) -> Result<usize, Error> {
+        let len = self.read_u32()?;
+        usize::try_from(len)
+            .map_err(|_| Error::InvalidSnapshot(format!("Length out of range: {}", len)))
+    }
+
+    pub(crate) fn read_f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+
+    pub(crate) fn read_point(&mut self) -> Result<Point, Error> {
+        Ok(Point::new(self.read_f64()?, self.read_f64()?))
+    }
+
+    pub(crate) fn read_vector(&mut self) -> Result<Vector2Df, Error> {
+        Ok(Vector2Df::new(self.read_f64()?, self.read_f64()?))
+    }
+
+    pub(crate) fn read_cell_range(&mut self) -> Result<CellRange, Error> {
+        let min = Vector2Di::new(self.read_i32()?, self.read_i32()?);
+        let max = Vector2Di::new(self.read_i32()?, self.read_i32()?);
+        Ok(CellRange::new(min, max))
+    }
+
+    pub(crate) fn read_string(&mut self) -> Result<String, Error> {
+        let len = self.read_len()?;
+        let (bytes, rest) = self
+            .bytes
+            .split_at_checked(len)
+            .ok_or_else(|| Error::InvalidSnapshot("Unexpected end of data".to_string()))?;
+        self.bytes = rest;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| Error::InvalidSnapshot("Invalid UTF-8 string".to_string()))
+    }
+
+    pub(crate) fn read_option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<Option<T>, Error> {
+        if self.read_bool()? {
+            read(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, snapshot::SnapshotWriter};
+
+    #[test]
+    fn lengths_fit_in_u32() {
+        let mut writer = SnapshotWriter::new();
+        writer
+            .write_len(u32::MAX as usize)
+            .expect("Largest u32 length should be written");
+        assert!(matches!(
+            writer.write_len(u32::MAX as usize + 1),
+            Err(Error::SnapshotLengthOverflow(len)) if len == u32::MAX as usize + 1
+        ));
+        assert_eq!(writer.into_bytes(), u32::MAX.to_le_bytes());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::byte_sink::ByteSink;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a hash over explicit little endian bytes, so hashes match across runs and platforms
+///
+/// Unlike `std::hash::Hasher`, the output is part of saved data and must never change
+pub(crate) struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        StableHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+impl ByteSink for StableHasher {
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{byte_sink::ByteSink as _, stable_hash::StableHasher};
+
+    #[test]
+    fn matches_fnv1a_reference() {
+        let mut hasher = StableHasher::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write_bytes(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+        let mut hasher = StableHasher::new();
+        hasher.write_bytes(b"foobar");
+        assert_eq!(hasher.finish(), 0x8594_4171_f739_67e8);
+    }
+}
@@ -0,0 +1,77 @@
+mod physics_trigger;
+
+pub use physics_trigger::{PhysicsTrigger, PhysicsTriggerBuilder, PhysicsTriggerEvent};
+
+use std::collections::BTreeMap;
+use vector2d::Vector2Df;
+
+use crate::{byte_sink::ByteSink as _, stable_hash::StableHasher};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TriggerId(usize);
+
+pub(crate) struct TriggerRegistry {
+    triggers: BTreeMap<TriggerId, PhysicsTrigger>,
+    next_id: usize,
+}
+
+impl TriggerRegistry {
+    pub(crate) fn new() -> Self {
+        TriggerRegistry {
+            triggers: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub(crate) fn add_trigger(&mut self, trigger: PhysicsTrigger) -> TriggerId {
+        let id = TriggerId(self.next_id);
+        self.next_id += 1;
+        self.triggers.insert(id, trigger);
+        id
+    }
+
+    pub(crate) fn get_trigger(&self, id: TriggerId) -> Option<&PhysicsTrigger> {
+        self.triggers.get(&id)
+    }
+
+    /// Replaces a trigger with a new trigger, preserving its id
+    ///
+    /// Returns the replaced trigger if the id was valid
+    pub(crate) fn replace_trigger(
+        &mut self,
+        id: TriggerId,
+        new_trigger: PhysicsTrigger,
+    ) -> Option<PhysicsTrigger> {
+        let trigger = self.triggers.get_mut(&id)?;
+        Some(std::mem::replace(trigger, new_trigger))
+    }
+
+    pub(crate) fn remove_trigger(&mut self, id: TriggerId) -> Option<PhysicsTrigger> {
+        self.triggers.remove(&id)
+    }
+
+    /// Hashes the id and props of every trigger
+    pub(crate) fn hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        for (trigger_id, trigger) in &self.triggers {
+            hasher.write_usize(trigger_id.0);
+            hasher.write_u32(trigger.start_frame());
+            hasher.write_u32(trigger.end_frame());
+            match trigger.event() {
+                PhysicsTriggerEvent::Gravity(gravity) => hasher.write_vector(gravity),
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Gravity of the most recently added gravity trigger active during a frame
+    pub(crate) fn gravity_at(&self, frame: u32) -> Option<Vector2Df> {
+        self.triggers
+            .values()
+            .rev()
+            .find_map(|trigger| match trigger.event() {
+                PhysicsTriggerEvent::Gravity(gravity) if trigger.is_active(frame) => Some(gravity),
+                PhysicsTriggerEvent::Gravity(_) => None,
+            })
+    }
+}
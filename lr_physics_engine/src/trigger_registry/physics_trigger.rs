@@ -0,0 +1,83 @@
+use vector2d::Vector2Df;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PhysicsTriggerEvent {
+    /// Replaces the engine's gravity while active
+    Gravity(Vector2Df),
+}
+
+/// Constructed trigger that holds props after building
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsTrigger {
+    start_frame: u32,
+    end_frame: u32,
+    event: PhysicsTriggerEvent,
+}
+
+impl PhysicsTrigger {
+    pub fn start_frame(&self) -> u32 {
+        self.start_frame
+    }
+
+    pub fn end_frame(&self) -> u32 {
+        self.end_frame
+    }
+
+    pub fn event(&self) -> PhysicsTriggerEvent {
+        self.event
+    }
+
+    /// Whether the trigger applies while simulating the step that produces `frame`
+    pub(crate) fn is_active(&self, frame: u32) -> bool {
+        self.start_frame <= frame && frame < self.end_frame
+    }
+}
+
+pub struct PhysicsTriggerBuilder {
+    start_frame: u32,
+    end_frame: u32,
+    event: PhysicsTriggerEvent,
+}
+
+impl PhysicsTriggerBuilder {
+    pub fn new(event: PhysicsTriggerEvent, start_frame: u32, end_frame: u32) -> Self {
+        PhysicsTriggerBuilder {
+            start_frame,
+            end_frame,
+            event,
+        }
+    }
+
+    pub fn start_frame(mut self, start_frame: u32) -> Self {
+        self.start_frame = start_frame;
+        self
+    }
+
+    pub fn end_frame(mut self, end_frame: u32) -> Self {
+        self.end_frame = end_frame;
+        self
+    }
+
+    pub fn event(mut self, event: PhysicsTriggerEvent) -> Self {
+        self.event = event;
+        self
+    }
+
+    pub fn build(self) -> PhysicsTrigger {
+        PhysicsTrigger {
+            start_frame: self.start_frame,
+            end_frame: self.end_frame,
+            event: self.event,
+        }
+    }
+}
+
+impl From<PhysicsTrigger> for PhysicsTriggerBuilder {
+    fn from(trigger: PhysicsTrigger) -> Self {
+        PhysicsTriggerBuilder {
+            start_frame: trigger.start_frame,
+            end_frame: trigger.end_frame,
+            event: trigger.event,
+        }
+    }
+}
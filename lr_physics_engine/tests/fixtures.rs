@@ -1,16 +1,22 @@
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
-    use geometry::Point;
-    use lr_format_core::Track;
+    use geometry::{Line, Point};
+    use lr_format_core::{Rider, RiderBuilder, Track};
     use lr_physics_engine::{
-        PhysicsEngine,
-        entity_registry::{EntityState, EntityTemplateBuilder, MountPhase, RemountVersion},
+        CachePolicy, Error, PhysicsEngine, PhysicsMoment,
+        browser_math::{self, JsEngine},
+        entity_registry::{
+            EntityBoneBuilder, EntityEvent, EntityId, EntityJointBuilder, EntityPointBuilder,
+            EntityState, EntityTemplateBuilder, EntityTemplateId, MountPhase, RemountVersion,
+        },
+        find_divergence,
         line_registry::PhysicsLineBuilder,
+        trigger_registry::{PhysicsTriggerBuilder, PhysicsTriggerEvent},
     };
     use lr_physics_grid::GridVersion;
     use serde::Deserialize;
-    use std::fs;
+    use std::{fs, iter::zip};
     use vector2d::Vector2Df;
 
     #[derive(Deserialize)]
@@ -34,67 +40,84 @@ mod tests {
         state: EngineTestCaseState,
     }
 
-    fn from_track(track: &Track, lra: bool) -> PhysicsEngine {
-        let grid_version = match track.grid_version() {
-            lr_format_core::GridVersion::V6_0 => GridVersion::V6_0,
-            lr_format_core::GridVersion::V6_1 => GridVersion::V6_1,
-            lr_format_core::GridVersion::V6_2 => GridVersion::V6_2,
-        };
-
-        let mut engine = PhysicsEngine::new(grid_version);
+    // Same engine as `PhysicsEngine::from_track`, but without riders or their templates
+    fn engine_with_lines(track: &Track) -> PhysicsEngine {
+        let mut engine = PhysicsEngine::new(GridVersion::from(track.grid_version()));
+        engine.set_frictionless(track.frictionless());
 
         for line in track.standard_lines() {
-            let physics_line = PhysicsLineBuilder::new(line.endpoints())
-                .flipped(line.flipped())
-                .left_extension(line.left_extension())
-                .right_extension(line.right_extension())
-                .height(line.height())
-                .acceleration_multiplier(line.multiplier())
-                .build();
-            engine.add_line(physics_line);
+            engine.add_line(PhysicsLineBuilder::from(line).build());
         }
 
-        let template_none_id = engine
-            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
-        let template_comv1_id = engine
-            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::ComV1));
-        let template_comv2_id = engine
-            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::ComV2));
-        let template_lra_id = engine
-            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::LRA));
+        engine
+    }
 
-        for rider in track.riders() {
-            let template_id = if lra {
-                template_lra_id
-            } else {
-                match rider.remount_version() {
-                    lr_format_core::RemountVersion::None => template_none_id,
-                    lr_format_core::RemountVersion::ComV1 => template_comv1_id,
-                    lr_format_core::RemountVersion::ComV2 => template_comv2_id,
-                    lr_format_core::RemountVersion::LRA => template_lra_id,
-                }
-            };
+    fn add_rider(
+        engine: &mut PhysicsEngine,
+        rider: &Rider,
+        template_id: EntityTemplateId,
+    ) -> EntityId {
+        let entity_id = engine
+            .add_entity(template_id)
+            .expect("Template id should be valid");
 
-            let entity_id = engine
-                .add_entity(template_id)
-                .expect("Template id should be valid");
+        if let Some(offset) = rider.start_offset() {
+            engine
+                .set_entity_initial_offset(entity_id, offset)
+                .expect("Entity id should be valid");
+        }
 
-            if let Some(offset) = rider.start_offset() {
-                engine
-                    .set_entity_initial_offset(entity_id, offset)
-                    .expect("Entity id should be valid");
-            }
+        if let Some(velocity) = rider.start_velocity() {
+            engine
+                .set_entity_initial_velocity(entity_id, velocity)
+                .expect("Entity id should be valid");
+        }
 
-            if let Some(velocity) = rider.start_velocity() {
-                engine
-                    .set_entity_initial_velocity(entity_id, velocity)
-                    .expect("Entity id should be valid");
-            }
+        if let Some(angle) = rider.start_angle() {
+            engine
+                .set_entity_initial_rotation(entity_id, angle)
+                .expect("Entity id should be valid");
         }
 
+        entity_id
+    }
+
+    fn from_track(track: &Track, lra: bool) -> PhysicsEngine {
+        let (engine, _) = PhysicsEngine::from_track(track, lra.then_some(RemountVersion::LRA));
         engine
     }
 
+    #[test]
+    fn engine_from_track() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/line_flags.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+
+        let (mut engine, line_ids) = PhysicsEngine::from_track(&track, None);
+        assert_eq!(line_ids.len(), track.standard_lines().len());
+        for (line_id, line) in zip(&line_ids, track.standard_lines()) {
+            let physics_line = engine.get_line(*line_id).unwrap();
+            assert_eq!(physics_line.endpoints(), line.endpoints());
+            assert_eq!(physics_line.flipped(), line.flipped());
+        }
+        let original_positions = first_entity_positions(&mut engine, 160);
+
+        let mut track_builder = lr_format_core::TrackBuilder::from(track);
+        track_builder.start_gravity(Vector2Df::new(0.0, -0.35));
+        let track = track_builder.build();
+        let (mut heavy_engine, _) = PhysicsEngine::from_track(&track, None);
+        assert_eq!(heavy_engine.gravity(), Vector2Df::new(0.0, -0.35));
+        assert_ne!(
+            first_entity_positions(&mut heavy_engine, 160),
+            original_positions
+        );
+
+        // Line ids let edits to the track's lines reach the engine
+        let first_line_id = *line_ids.first().unwrap();
+        heavy_engine.remove_line(first_line_id).unwrap();
+        assert!(heavy_engine.get_line(first_line_id).is_none());
+    }
+
     #[test]
     fn engine_fixtures() {
         let data = fs::read_to_string("../fixtures/lr_physics_engine/tests/fixture_data.json")
@@ -124,6 +147,1231 @@ mod tests {
         }
     }
 
+    fn first_entity_positions(engine: &mut PhysicsEngine, frame: u32) -> Vec<Point> {
+        engine.view_frame(frame).first().unwrap().point_positions()
+    }
+
+    #[test]
+    fn custom_gravity() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let entity_id = engine.add_entity(template_id).unwrap();
+        engine
+            .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+            .unwrap();
+        let initial_positions = first_entity_positions(&mut engine, 0);
+
+        assert_eq!(engine.gravity(), Vector2Df::new(0.0, -0.175));
+        assert_ne!(first_entity_positions(&mut engine, 40), initial_positions);
+
+        // Changing gravity should invalidate frames that were already simulated
+        engine.set_gravity(Vector2Df::zero());
+        assert_eq!(first_entity_positions(&mut engine, 40), initial_positions);
+
+        engine.set_gravity(Vector2Df::new(0.175, 0.0));
+        let positions = first_entity_positions(&mut engine, 1);
+        for (position, initial_position) in positions.iter().zip(&initial_positions) {
+            assert!(position.x() > initial_position.x());
+        }
+    }
+
+    #[test]
+    fn template_gravity_overrides_engine_gravity() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template =
+            EntityTemplateBuilder::from(EntityTemplateBuilder::default_rider(RemountVersion::None))
+                .gravity(Vector2Df::zero())
+                .build()
+                .unwrap();
+        let template_id = engine.register_entity_template(template);
+        let entity_id = engine.add_entity(template_id).unwrap();
+        engine
+            .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+            .unwrap();
+        let initial_positions = first_entity_positions(&mut engine, 0);
+
+        engine.set_gravity(Vector2Df::new(0.0, -1.0));
+        assert_eq!(first_entity_positions(&mut engine, 40), initial_positions);
+    }
+
+    #[test]
+    fn gravity_triggers() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let entity_id = engine.add_entity(template_id).unwrap();
+        engine
+            .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+            .unwrap();
+        let initial_positions = first_entity_positions(&mut engine, 0);
+        let falling_positions = first_entity_positions(&mut engine, 40);
+
+        let trigger_id = engine.add_trigger(
+            PhysicsTriggerBuilder::new(PhysicsTriggerEvent::Gravity(Vector2Df::zero()), 1, 41)
+                .build(),
+        );
+        assert_eq!(first_entity_positions(&mut engine, 40), initial_positions);
+        assert_ne!(first_entity_positions(&mut engine, 41), initial_positions);
+
+        // Moving the trigger later should keep the frames before it
+        engine.replace_trigger(
+            trigger_id,
+            PhysicsTriggerBuilder::new(PhysicsTriggerEvent::Gravity(Vector2Df::zero()), 41, 81)
+                .build(),
+        );
+        assert_eq!(first_entity_positions(&mut engine, 40), falling_positions);
+
+        engine.remove_trigger(trigger_id);
+        assert_eq!(first_entity_positions(&mut engine, 40), falling_positions);
+    }
+
+    fn first_entity_moment_positions(
+        engine: &mut PhysicsEngine,
+        frame: u32,
+        moment: PhysicsMoment,
+    ) -> Vec<Point> {
+        engine
+            .view_moment(frame, moment)
+            .first()
+            .unwrap()
+            .point_positions()
+    }
+
+    #[test]
+    fn moments() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let entity_id = engine.add_entity(template_id).unwrap();
+        engine
+            .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+            .unwrap();
+        let initial_positions = first_entity_positions(&mut engine, 0);
+
+        assert_eq!(
+            first_entity_moment_positions(&mut engine, 0, PhysicsMoment::Momentum),
+            initial_positions
+        );
+
+        let expected_momentum_positions: Vec<Point> = initial_positions
+            .iter()
+            .map(|position| position.translated_by(Vector2Df::new(0.0, 0.175)))
+            .collect();
+        assert_eq!(
+            first_entity_moment_positions(&mut engine, 1, PhysicsMoment::Momentum),
+            expected_momentum_positions
+        );
+
+        let frame_positions = first_entity_positions(&mut engine, 30);
+        let next_frame_positions = first_entity_positions(&mut engine, 31);
+
+        // Viewing moments should not affect cached frames
+        first_entity_moment_positions(&mut engine, 31, PhysicsMoment::Iteration(2));
+        first_entity_moment_positions(&mut engine, 20, PhysicsMoment::Momentum);
+        assert_eq!(first_entity_positions(&mut engine, 30), frame_positions);
+        assert_eq!(
+            first_entity_positions(&mut engine, 31),
+            next_frame_positions
+        );
+
+        // Nothing after the flutter step moves points of a single entity
+        assert_eq!(
+            first_entity_moment_positions(&mut engine, 31, PhysicsMoment::Flutter),
+            next_frame_positions
+        );
+        assert_eq!(
+            first_entity_moment_positions(&mut engine, 31, PhysicsMoment::Iteration(5)),
+            first_entity_moment_positions(&mut engine, 31, PhysicsMoment::Iteration(200))
+        );
+    }
+
+    #[test]
+    fn initial_rotation() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let entity_id = engine.add_entity(template_id).unwrap();
+        let offset = Vector2Df::new(12.0, -3.0);
+        engine.set_entity_initial_offset(entity_id, offset).unwrap();
+        let unrotated_positions = first_entity_positions(&mut engine, 0);
+
+        engine.set_entity_initial_rotation(entity_id, 0.0).unwrap();
+        assert_eq!(first_entity_positions(&mut engine, 0), unrotated_positions);
+
+        // Half a turn mirrors every point through the initial offset
+        engine
+            .set_entity_initial_rotation(entity_id, 180.0)
+            .unwrap();
+        assert_eq!(engine.get_entity_initial_rotation(entity_id), Some(180.0));
+        let rotated_positions = first_entity_positions(&mut engine, 0);
+        for (rotated, unrotated) in zip(&rotated_positions, &unrotated_positions) {
+            let expected = Point::new(
+                2.0 * offset.x() - unrotated.x(),
+                2.0 * offset.y() - unrotated.y(),
+            );
+            assert!(rotated.distance_squared_from(expected) < 1e-20);
+        }
+
+        // Chrome and Firefox round this angle's sine or cosine differently in the last bit
+        let angle = 10.2;
+        let radians = browser_math::degrees_to_radians(angle);
+        let mut browser_positions = Vec::new();
+        for js_engine in [JsEngine::V8, JsEngine::SpiderMonkey] {
+            let template = EntityTemplateBuilder::from(EntityTemplateBuilder::default_rider(
+                RemountVersion::None,
+            ))
+            .js_engine(js_engine)
+            .build()
+            .unwrap();
+            let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+            let template_id = engine.register_entity_template(template);
+            let entity_id = engine.add_entity(template_id).unwrap();
+            let unrotated_positions = first_entity_positions(&mut engine, 0);
+            engine
+                .set_entity_initial_rotation(entity_id, angle)
+                .unwrap();
+
+            let (sin, cos) = (js_engine.sin(radians), js_engine.cos(radians));
+            let expected_positions: Vec<Point> = unrotated_positions
+                .iter()
+                .map(|point| {
+                    Point::new(
+                        point.x() * cos - point.y() * sin,
+                        point.x() * sin + point.y() * cos,
+                    )
+                })
+                .collect();
+            let rotated_positions = first_entity_positions(&mut engine, 0);
+            assert_eq!(rotated_positions, expected_positions);
+            browser_positions.push(rotated_positions);
+        }
+        assert_ne!(browser_positions.first(), browser_positions.last());
+    }
+
+    #[test]
+    fn ids_are_not_reused() {
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let other_template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::LRA));
+        assert_ne!(template_id, other_template_id);
+
+        let entity0_id = engine.add_entity(template_id).unwrap();
+        let entity1_id = engine.add_entity(template_id).unwrap();
+        engine.remove_entity(entity0_id).unwrap();
+        let entity2_id = engine.add_entity(template_id).unwrap();
+        assert_ne!(entity2_id, entity0_id);
+        assert_ne!(entity2_id, entity1_id);
+        assert!(engine.remove_entity(entity0_id).is_err());
+        assert!(engine.get_entity_initial_offset(entity1_id).is_some());
+
+        let line =
+            || PhysicsLineBuilder::new(Line::new(Point::zero(), Point::new(10.0, 0.0))).build();
+        let line0_id = engine.add_line(line());
+        engine.remove_line(line0_id).unwrap();
+        let line1_id = engine.add_line(line());
+        assert!(line0_id != line1_id);
+        assert!(engine.get_line(line0_id).is_none());
+        assert!(engine.replace_line(line0_id, line()).is_err());
+        assert!(engine.remove_line(line0_id).is_err());
+        assert!(engine.get_line(line1_id).is_some());
+    }
+
+    #[test]
+    fn line_edits_invalidate_cache() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/line_flags.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let target_frame = 160;
+
+        let mut engine = from_track(&track, false);
+        let original_positions = first_entity_positions(&mut engine, target_frame);
+        assert_eq!(engine.cache_usage().cached_frames(), 160);
+        let far_line = PhysicsLineBuilder::new(Line::new(
+            Point::new(100_000.0, 0.0),
+            Point::new(100_100.0, 0.0),
+        ))
+        .build();
+        let far_line_id = engine.add_line(far_line);
+        // Invalidated frames are dropped the next time frames are viewed
+        engine.view_frame(0);
+        assert_eq!(
+            engine.cache_usage().cached_frames(),
+            160,
+            "far edit should keep every cached frame"
+        );
+        assert_eq!(
+            first_entity_positions(&mut engine, target_frame),
+            original_positions
+        );
+
+        let midway_position = *first_entity_positions(&mut engine, 80).first().unwrap();
+        let near_line = || {
+            PhysicsLineBuilder::new(Line::new(
+                midway_position.translated_by(Vector2Df::new(-20.0, 5.0)),
+                midway_position.translated_by(Vector2Df::new(20.0, 5.0)),
+            ))
+            .build()
+        };
+        let near_line_id = engine.add_line(near_line());
+        engine.view_frame(0);
+        let kept_frames = engine.cache_usage().cached_frames();
+
+        let mut reference_engine = from_track(&track, false);
+        let mut fresh_engine = from_track(&track, false);
+        fresh_engine.add_line(near_line());
+        let first_affected_frame = (0..=target_frame)
+            .find(|frame| {
+                first_entity_positions(&mut fresh_engine, *frame)
+                    != first_entity_positions(&mut reference_engine, *frame)
+            })
+            .unwrap();
+        assert!(
+            0 < kept_frames && kept_frames < first_affected_frame as usize,
+            "near edit should only drop frames from {first_affected_frame} onward, kept {kept_frames}",
+        );
+
+        let edited_positions = first_entity_positions(&mut engine, target_frame);
+        assert_ne!(edited_positions, original_positions);
+        assert_eq!(
+            first_entity_positions(&mut fresh_engine, target_frame),
+            edited_positions
+        );
+
+        engine.remove_line(near_line_id).unwrap();
+        engine.remove_line(far_line_id).unwrap();
+        assert_eq!(
+            first_entity_positions(&mut engine, target_frame),
+            original_positions
+        );
+    }
+
+    #[test]
+    fn line_edits_near_separate_entities() {
+        let mut builder = EntityTemplateBuilder::new();
+        builder.add_point(EntityPointBuilder::new(Point::zero()).is_contact(true));
+        let template = builder.gravity(Vector2Df::zero()).build().unwrap();
+
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine.register_entity_template(template);
+        // Resting in the middle of cell (0, 0) and cell (1000, 0), lines are queried from
+        // cells -1 to 1 around the first and 999 to 1001 around the second
+        for offset in [Vector2Df::new(7.0, 7.0), Vector2Df::new(14_007.0, 7.0)] {
+            let entity_id = engine.add_entity(template_id).unwrap();
+            engine.set_entity_initial_offset(entity_id, offset).unwrap();
+            engine
+                .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+                .unwrap();
+        }
+        engine.view_frame(40);
+        assert_eq!(engine.cache_usage().cached_frames(), 40);
+
+        let vertical_line =
+            |x: f64| PhysicsLineBuilder::new(Line::new(Point::new(x, 0.0), Point::new(x, 13.0)));
+
+        engine.add_line(vertical_line(7_000.0).build());
+        engine.view_frame(0);
+        assert_eq!(
+            engine.cache_usage().cached_frames(),
+            40,
+            "edit between entities should keep every cached frame"
+        );
+
+        // Cell 2 starts at 28, just outside of the cells queried around the first entity
+        let line_id = engine.add_line(vertical_line(28.0).build());
+        engine.view_frame(0);
+        assert_eq!(
+            engine.cache_usage().cached_frames(),
+            40,
+            "edit just outside of queried cells should keep every cached frame"
+        );
+
+        engine
+            .replace_line(line_id, vertical_line(27.9).build())
+            .unwrap();
+        engine.view_frame(0);
+        assert_eq!(
+            engine.cache_usage().cached_frames(),
+            0,
+            "edit just inside of queried cells should drop every cached frame"
+        );
+    }
+
+    #[test]
+    fn frictionless() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/line_flags.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let target_frame = 160;
+
+        let mut engine = from_track(&track, false);
+        assert!(!engine.frictionless());
+        let original_positions = first_entity_positions(&mut engine, target_frame);
+
+        engine.set_frictionless(true);
+        assert!(engine.frictionless());
+        assert_ne!(
+            first_entity_positions(&mut engine, target_frame),
+            original_positions
+        );
+
+        // Airborne frames are unaffected
+        let mut reference_engine = from_track(&track, false);
+        assert_eq!(
+            first_entity_positions(&mut engine, 1),
+            first_entity_positions(&mut reference_engine, 1)
+        );
+
+        let mut frictionless_track = lr_format_core::TrackBuilder::from(track);
+        frictionless_track.frictionless(true);
+        let mut track_engine = from_track(&frictionless_track.build(), false);
+        assert!(track_engine.frictionless());
+        assert_eq!(
+            first_entity_positions(&mut track_engine, target_frame),
+            first_entity_positions(&mut engine, target_frame)
+        );
+
+        engine.set_frictionless(false);
+        assert_eq!(
+            first_entity_positions(&mut engine, target_frame),
+            original_positions
+        );
+    }
+
+    #[test]
+    fn event_log() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/dismount.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let target_frame = 58;
+
+        let mut reference_engine = from_track(&track, false);
+        let mut engine = from_track(&track, false);
+        assert!(!engine.event_log());
+        engine.set_event_log(true);
+        assert!(engine.event_log());
+
+        let mut contacts = 0;
+        let mut dismount_frame = None;
+        let mut sled_break_frame = None;
+
+        for frame in 0..=target_frame {
+            let reference_state = reference_engine.view_frame(frame).pop().unwrap();
+            let state = engine.view_frame(frame).pop().unwrap();
+            assert_eq!(state.point_positions(), reference_state.point_positions());
+            assert!(reference_state.events().is_empty());
+
+            for event in state.events() {
+                match event {
+                    EntityEvent::Contact {
+                        line_id, iteration, ..
+                    } => {
+                        assert!(engine.get_line(*line_id).is_some());
+                        assert!(*iteration < 6);
+                        contacts += 1;
+                    }
+                    EntityEvent::BoneBreak(_) | EntityEvent::Dismount(_) => {
+                        assert!(dismount_frame.is_none(), "rider dismounts once");
+                        dismount_frame = Some(frame);
+                    }
+                    EntityEvent::SledBreak(_) => {
+                        sled_break_frame.get_or_insert(frame);
+                    }
+                }
+            }
+        }
+
+        assert!(contacts > 0);
+
+        // Events line up with the frames where the state changes
+        let dismount_frame = dismount_frame.unwrap();
+        assert!(
+            engine
+                .view_frame(dismount_frame - 1)
+                .pop()
+                .unwrap()
+                .mount_phase()
+                .is_mounted()
+        );
+        assert!(
+            !engine
+                .view_frame(dismount_frame)
+                .pop()
+                .unwrap()
+                .mount_phase()
+                .is_mounted()
+        );
+        let sled_break_frame = sled_break_frame.unwrap();
+        assert!(
+            engine
+                .view_frame(sled_break_frame - 1)
+                .pop()
+                .unwrap()
+                .sled_intact()
+        );
+        assert!(
+            !engine
+                .view_frame(sled_break_frame)
+                .pop()
+                .unwrap()
+                .sled_intact()
+        );
+
+        engine.set_event_log(false);
+        assert!(
+            engine
+                .view_frame(dismount_frame)
+                .pop()
+                .unwrap()
+                .events()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn cache_policy() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/line_flags.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+
+        let mut reference_engine = from_track(&track, false);
+        let expected_positions: Vec<Vec<Point>> = (0..=160)
+            .map(|frame| first_entity_positions(&mut reference_engine, frame))
+            .collect();
+        let expected = |frame: u32| expected_positions.get(frame as usize).unwrap();
+        assert_eq!(reference_engine.cache_usage().cached_frames(), 160);
+
+        let mut engine = from_track(&track, false);
+        engine.set_cache_policy(CachePolicy::new(10, 5, None));
+        assert_eq!(first_entity_positions(&mut engine, 160), *expected(160));
+        assert_eq!(engine.cache_usage().cached_frames(), 16 + 5);
+
+        for frame in [55, 3, 159, 0, 101] {
+            assert_eq!(
+                first_entity_positions(&mut engine, frame),
+                *expected(frame),
+                "frame {frame} should be resimulated from a keyframe",
+            );
+        }
+
+        let mut engine = from_track(&track, false);
+        first_entity_positions(&mut engine, 8);
+        let memory_budget = engine.cache_usage().estimated_bytes();
+        engine.set_cache_policy(CachePolicy::new(1, 0, Some(memory_budget)));
+        assert_eq!(first_entity_positions(&mut engine, 160), *expected(160));
+        assert!(engine.cache_usage().estimated_bytes() <= memory_budget);
+        assert_eq!(first_entity_positions(&mut engine, 77), *expected(77));
+    }
+
+    fn assert_same_states(result: &[EntityState], expected: &[EntityState]) {
+        assert_eq!(result.len(), expected.len());
+        for (state, expected_state) in zip(result, expected) {
+            assert_eq!(state.point_positions(), expected_state.point_positions());
+            assert_eq!(state.point_velocities(), expected_state.point_velocities());
+            assert_eq!(state.mount_phase(), expected_state.mount_phase());
+            assert_eq!(state.sled_intact(), expected_state.sled_intact());
+            assert_eq!(state.events(), expected_state.events());
+        }
+    }
+
+    #[test]
+    fn snapshots() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/dismount.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+
+        let mut engine = engine_with_lines(&track);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::ComV2));
+        let rider_id = add_rider(&mut engine, track.riders().first().unwrap(), template_id);
+        engine.set_event_log(true);
+        engine.set_gravity(Vector2Df::new(0.05, 0.15));
+        let snapshot = engine.export_snapshot(&[80, 40, 0, 40]).unwrap();
+
+        let mut imported_engine = engine_with_lines(&track);
+        imported_engine.set_gravity(engine.gravity());
+        imported_engine.import_snapshot(&snapshot).unwrap();
+        assert!(imported_engine.event_log());
+        assert_eq!(
+            imported_engine.get_entity_point_id(rider_id, "scarf6"),
+            engine.get_entity_point_id(rider_id, "scarf6")
+        );
+        assert_eq!(imported_engine.cache_usage().cached_frames(), 2);
+
+        for frame in [40, 80] {
+            assert_same_states(
+                &imported_engine.view_frame(frame),
+                &engine.view_frame(frame),
+            );
+        }
+        assert_eq!(
+            imported_engine.cache_usage().cached_frames(),
+            2,
+            "snapshot frames shouldn't be resimulated"
+        );
+
+        for frame in [0, 20, 60, 120] {
+            assert_same_states(
+                &imported_engine.view_frame(frame),
+                &engine.view_frame(frame),
+            );
+        }
+
+        let far_line = PhysicsLineBuilder::new(Line::new(
+            Point::new(100_000.0, 0.0),
+            Point::new(100_100.0, 0.0),
+        ))
+        .build();
+        let mut other_engine = engine_with_lines(&track);
+        other_engine.add_line(far_line);
+        assert!(matches!(
+            other_engine.import_snapshot(&snapshot),
+            Err(Error::SnapshotLineMismatch)
+        ));
+
+        // Anything else the cached frames depend on has to match too
+        let mut other_engine = engine_with_lines(&track);
+        other_engine.set_gravity(engine.gravity());
+        other_engine.add_trigger(
+            PhysicsTriggerBuilder::new(PhysicsTriggerEvent::Gravity(Vector2Df::zero()), 1, 41)
+                .build(),
+        );
+        assert!(matches!(
+            other_engine.import_snapshot(&snapshot),
+            Err(Error::SnapshotTriggerMismatch)
+        ));
+
+        let mut other_engine = engine_with_lines(&track);
+        other_engine.set_gravity(engine.gravity());
+        other_engine.set_grid_version(GridVersion::V6_1);
+        assert!(matches!(
+            other_engine.import_snapshot(&snapshot),
+            Err(Error::SnapshotSettingMismatch(_))
+        ));
+
+        let mut other_engine = engine_with_lines(&track);
+        other_engine.set_gravity(engine.gravity());
+        other_engine.set_frictionless(true);
+        assert!(matches!(
+            other_engine.import_snapshot(&snapshot),
+            Err(Error::SnapshotSettingMismatch(_))
+        ));
+
+        let mut other_engine = engine_with_lines(&track);
+        assert!(matches!(
+            other_engine.import_snapshot(&snapshot),
+            Err(Error::SnapshotSettingMismatch(_))
+        ));
+        assert!(other_engine.view_frame(0).is_empty());
+
+        let mut future_snapshot = snapshot.clone();
+        *future_snapshot.get_mut(4).unwrap() = 2;
+        assert!(matches!(
+            imported_engine.import_snapshot(&future_snapshot),
+            Err(Error::UnsupportedSnapshotVersion(2))
+        ));
+
+        let truncated_snapshot = snapshot.get(..snapshot.len() - 1).unwrap();
+        assert!(matches!(
+            imported_engine.import_snapshot(truncated_snapshot),
+            Err(Error::InvalidSnapshot(_))
+        ));
+        assert!(matches!(
+            imported_engine.import_snapshot(b"LRPF"),
+            Err(Error::InvalidSnapshot(_))
+        ));
+        assert_same_states(&imported_engine.view_frame(80), &engine.view_frame(80));
+    }
+
+    #[test]
+    fn state_hashes() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/line_flags.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let target_frame = 160;
+
+        let mut engine = from_track(&track, false);
+        let hashes: Vec<u64> = (0..=target_frame)
+            .map(|frame| engine.frame_hash(frame))
+            .collect();
+        // Hashes are stable across runs and platforms, so a change here means physics changed
+        assert_eq!(engine.frame_hash(target_frame), 0xb420_db0f_9e25_d789);
+        assert_eq!(engine.rolling_hash(0..=target_frame), 0x3bda_89f6_e220_d017);
+
+        let mut logged_engine = from_track(&track, false);
+        logged_engine.set_event_log(true);
+        logged_engine.set_cache_policy(CachePolicy::new(10, 0, None));
+        for frame in (0..=target_frame).rev() {
+            assert_eq!(
+                logged_engine.frame_hash(frame),
+                *hashes.get(frame as usize).unwrap()
+            );
+        }
+        assert_eq!(
+            logged_engine.rolling_hash(0..=target_frame),
+            engine.rolling_hash(0..=target_frame)
+        );
+
+        let midway_position = *first_entity_positions(&mut engine, 80).first().unwrap();
+        engine.add_line(
+            PhysicsLineBuilder::new(Line::new(
+                midway_position.translated_by(Vector2Df::new(-20.0, 5.0)),
+                midway_position.translated_by(Vector2Df::new(20.0, 5.0)),
+            ))
+            .build(),
+        );
+        let first_divergent_frame = (0..=target_frame)
+            .find(|frame| engine.frame_hash(*frame) != *hashes.get(*frame as usize).unwrap())
+            .unwrap();
+        let first_moved_frame = (0..=target_frame)
+            .find(|frame| {
+                first_entity_positions(&mut engine, *frame)
+                    != first_entity_positions(&mut logged_engine, *frame)
+            })
+            .unwrap();
+        assert!(first_divergent_frame <= first_moved_frame);
+        assert_ne!(
+            engine.rolling_hash(0..=target_frame),
+            logged_engine.rolling_hash(0..=target_frame)
+        );
+        assert_eq!(
+            engine.rolling_hash(0..=first_divergent_frame - 1),
+            logged_engine.rolling_hash(0..=first_divergent_frame - 1)
+        );
+    }
+
+    #[test]
+    fn divergence() {
+        let read_track = |name: &str| {
+            let file = fs::read(format!(
+                "../fixtures/lr_physics_engine/tests/{name}.track.json"
+            ))
+            .expect("Failed to read JSON file");
+            lr_format_json::read(&file).expect("Failed to parse track file")
+        };
+
+        let track = read_track("grid_62");
+        let mut first = from_track(&track, false);
+        let mut second = from_track(&track, false);
+        assert!(
+            find_divergence(&mut first, &mut second, 0..=200)
+                .unwrap()
+                .is_none()
+        );
+
+        first.set_grid_version(GridVersion::V6_1);
+        let divergence = find_divergence(&mut first, &mut second, 0..=200)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.frame(), 60);
+        assert_eq!(divergence.entity_index(), 0);
+
+        // Events are found without turning on the event log or dropping cached frames
+        assert!(!first.event_log());
+        assert_eq!(first.cache_usage().cached_frames(), 60);
+        let mut logged = from_track(&track, false);
+        logged.set_grid_version(GridVersion::V6_1);
+        logged.set_event_log(true);
+        assert_eq!(
+            logged.view_frame(60).first().unwrap().events(),
+            divergence.first_events()
+        );
+        assert_eq!(
+            first.frame_hash(divergence.frame() - 1),
+            second.frame_hash(divergence.frame() - 1)
+        );
+        assert_ne!(
+            first.frame_hash(divergence.frame()),
+            second.frame_hash(divergence.frame())
+        );
+        let (first_contact, second_contact) = divergence.split_contacts().unwrap();
+        for contact in [first_contact.unwrap(), second_contact.unwrap()] {
+            assert!(matches!(
+                contact,
+                EntityEvent::Contact { line_id, .. } if first.get_line(line_id).is_some()
+            ));
+        }
+
+        let track = read_track("grid_testing");
+        let mut first = from_track(&track, false);
+        let mut second = from_track(&track, false);
+        first.set_grid_version(GridVersion::V6_1);
+        second.set_grid_version(GridVersion::V6_2);
+        let divergence = find_divergence(&mut first, &mut second, 0..=200)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.frame(), 1);
+        assert!(divergence.max_deviation() > 0.0);
+        let deviating_point_id = divergence.deviating_point_id().unwrap();
+        let position = |engine: &mut PhysicsEngine| {
+            engine
+                .view_frame(1)
+                .first()
+                .unwrap()
+                .point_states()
+                .get(&deviating_point_id)
+                .unwrap()
+                .position()
+        };
+        assert_eq!(
+            position(&mut first)
+                .distance_from(position(&mut second))
+                .to_bits(),
+            divergence.max_deviation().to_bits()
+        );
+
+        let track = read_track("dismount_remountv2");
+        let mut first = from_track(&track, false);
+        let mut second = from_track(&track, true);
+        first.set_event_log(true);
+        let divergence = find_divergence(&mut first, &mut second, 0..=200)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.frame(), 51);
+        assert!(first.event_log());
+        assert!(!second.event_log());
+        assert!(divergence.split_contacts().is_none());
+        assert_ne!(
+            first.view_frame(51).first().unwrap().sled_intact(),
+            second.view_frame(51).first().unwrap().sled_intact()
+        );
+
+        let template_id = second
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::LRA));
+        second.add_entity(template_id);
+        assert!(matches!(
+            find_divergence(&mut first, &mut second, 0..=200),
+            Err(Error::EntityCountMismatch(1, 2))
+        ));
+    }
+
+    #[test]
+    fn parallel_entities() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/remount_two_riders.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let target_frame = 90;
+
+        // Enough stacked riders to split between threads, swapping sleds across them
+        let mut track_builder = lr_format_core::TrackBuilder::from(track);
+        let riders = track_builder.riders();
+        riders.clear();
+        for i in 0..20 {
+            for start_y in [0.0, -50.0] {
+                let mut rider = RiderBuilder::new(lr_format_core::RemountVersion::ComV2);
+                rider
+                    .start_offset(Vector2Df::new(f64::from(i) * 0.01, start_y))
+                    .start_velocity(Vector2Df::new(0.4, 0.0));
+                riders.push(rider);
+            }
+        }
+        let track = track_builder.build();
+
+        let mut serial_engine = from_track(&track, false);
+        serial_engine.set_max_threads(1);
+        serial_engine.set_event_log(true);
+        let mut parallel_engine = from_track(&track, false);
+        parallel_engine.set_max_threads(4);
+        parallel_engine.set_event_log(true);
+        assert_eq!(parallel_engine.max_threads(), 4);
+
+        let original_hash = serial_engine.rolling_hash(0..=target_frame);
+        assert_eq!(
+            parallel_engine.rolling_hash(0..=target_frame),
+            original_hash
+        );
+        for frame in [60, 80] {
+            assert_same_states(
+                &parallel_engine.view_frame(frame),
+                &serial_engine.view_frame(frame),
+            );
+        }
+        assert!(
+            serial_engine
+                .view_frame(80)
+                .iter()
+                .any(|state| state.mount_phase().is_remounting())
+        );
+        assert_same_states(
+            &parallel_engine.view_moment(target_frame, PhysicsMoment::Iteration(2)),
+            &serial_engine.view_moment(target_frame, PhysicsMoment::Iteration(2)),
+        );
+
+        // Line query cells recorded on other threads still invalidate the right frames
+        let midway_position = *first_entity_positions(&mut serial_engine, 60)
+            .first()
+            .unwrap();
+        let near_line = || {
+            PhysicsLineBuilder::new(Line::new(
+                midway_position.translated_by(Vector2Df::new(-20.0, 5.0)),
+                midway_position.translated_by(Vector2Df::new(20.0, 5.0)),
+            ))
+            .build()
+        };
+        serial_engine.add_line(near_line());
+        serial_engine.clear_cache();
+        parallel_engine.add_line(near_line());
+        let edited_hash = serial_engine.rolling_hash(0..=target_frame);
+        assert_ne!(edited_hash, original_hash);
+        assert_eq!(parallel_engine.rolling_hash(0..=target_frame), edited_hash);
+    }
+
+    #[test]
+    fn custom_skeleton() {
+        let mut builder = EntityTemplateBuilder::new();
+        let left =
+            builder.add_point(EntityPointBuilder::new(Point::new(0.0, 0.0)).is_contact(true));
+        let right =
+            builder.add_point(EntityPointBuilder::new(Point::new(10.0, 0.0)).is_contact(true));
+        let top = builder.add_point(EntityPointBuilder::new(Point::new(5.0, -5.0)));
+        builder.add_bone(EntityBoneBuilder::new((left, right)));
+        builder.add_bone(EntityBoneBuilder::new((right, top)));
+        builder.add_bone(EntityBoneBuilder::new((top, left)));
+        let template = builder.build().unwrap();
+
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        engine.add_line(
+            PhysicsLineBuilder::new(Line::new(Point::new(-20.0, 5.0), Point::new(30.0, 5.0)))
+                .build(),
+        );
+        let template_id = engine.register_entity_template(template);
+        let entity_id = engine.add_entity(template_id).unwrap();
+        engine
+            .set_entity_initial_velocity(entity_id, Vector2Df::zero())
+            .unwrap();
+
+        // Contact points come to rest on the line instead of falling through
+        let positions = first_entity_positions(&mut engine, 80);
+        assert_eq!(positions.len(), 3);
+        for position in positions {
+            assert!(position.y() > 0.0 && position.y() < 6.0);
+        }
+    }
+
+    #[test]
+    fn invalid_custom_skeleton() {
+        let mut other_builder = EntityTemplateBuilder::new();
+        other_builder.add_point(EntityPointBuilder::new(Point::zero()));
+        let other_point = other_builder.add_point(EntityPointBuilder::new(Point::zero()));
+        other_builder.add_bone(EntityBoneBuilder::new((other_point, other_point)));
+        let other_bone = other_builder.add_bone(EntityBoneBuilder::new((other_point, other_point)));
+
+        let mut builder = EntityTemplateBuilder::new();
+        let point = builder.add_point(EntityPointBuilder::new(Point::zero()));
+        let bone = builder.add_bone(EntityBoneBuilder::new((point, other_point)));
+        assert!(matches!(
+            builder.build(),
+            Err(Error::BonePointNotFound(bone_id, point_id))
+                if bone_id == bone && point_id == other_point
+        ));
+
+        let mut builder = EntityTemplateBuilder::new();
+        let point = builder.add_point(EntityPointBuilder::new(Point::zero()));
+        let bone = builder.add_bone(EntityBoneBuilder::new((point, point)));
+        let joint = builder.add_joint(EntityJointBuilder::new(bone, other_bone));
+        assert!(matches!(
+            builder.build(),
+            Err(Error::JointBoneNotFound(joint_id, bone_id))
+                if joint_id == joint && bone_id == other_bone
+        ));
+
+        let mut builder = EntityTemplateBuilder::new();
+        builder.add_point(EntityPointBuilder::new(Point::zero()).name("peg".to_string()));
+        builder.add_point(EntityPointBuilder::new(Point::zero()).name("peg".to_string()));
+        assert!(matches!(
+            builder.build(),
+            Err(Error::DuplicatePointName(name)) if name == "peg"
+        ));
+    }
+
+    #[test]
+    fn named_points() {
+        let template = EntityTemplateBuilder::default_rider(RemountVersion::None);
+        let shoulder_id = template.point_id("shoulder").unwrap();
+        assert_eq!(template.point_name(shoulder_id), Some("shoulder"));
+        assert_eq!(template.point_id("elbow"), None);
+
+        let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+        let template_id = engine.register_entity_template(template);
+        let entity_id = engine.add_entity(template_id).unwrap();
+        assert_eq!(
+            engine.get_entity_point_id(entity_id, "shoulder"),
+            Some(shoulder_id)
+        );
+
+        // Keyed states line up with the positional vectors
+        let state = engine.view_frame(30).pop().unwrap();
+        let shoulder = state.point_states().get(&shoulder_id).unwrap();
+        assert_eq!(state.point_positions().get(5), Some(&shoulder.position()));
+        assert_eq!(state.point_velocities().get(5), Some(&shoulder.velocity()));
+        assert_eq!(
+            shoulder.computed_previous_position(),
+            engine
+                .view_frame(29)
+                .pop()
+                .unwrap()
+                .point_states()
+                .get(&shoulder_id)
+                .unwrap()
+                .position()
+        );
+    }
+
+    #[test]
+    fn bone_states() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/dismount.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let mut engine = engine_with_lines(&track);
+        let template_id = engine
+            .register_entity_template(EntityTemplateBuilder::default_rider(RemountVersion::None));
+        let entity_id = add_rider(&mut engine, track.riders().first().unwrap(), template_id);
+        engine.set_event_log(true);
+
+        let initial_bones = engine.view_entity_bones(0, entity_id).unwrap();
+        assert_eq!(initial_bones.len(), 29);
+        for bone in initial_bones.values() {
+            assert!(bone.intact());
+            // Repel bones rest at half their initial length
+            assert!([1.0, 2.0].contains(&bone.stretch_ratio()));
+        }
+
+        let (dismount_frame, broken_bone_id) = (1..=58)
+            .find_map(|frame| {
+                engine
+                    .view_frame(frame)
+                    .pop()
+                    .unwrap()
+                    .events()
+                    .iter()
+                    .find_map(|event| match event {
+                        EntityEvent::BoneBreak(bone_id) => Some((frame, *bone_id)),
+                        _ => None,
+                    })
+            })
+            .unwrap();
+        let broken_bone = engine
+            .view_entity_bones(dismount_frame, entity_id)
+            .unwrap()
+            .remove(&broken_bone_id)
+            .unwrap();
+        assert!(broken_bone.stretch_ratio() > 1.0);
+        assert!(!broken_bone.intact());
+
+        // Breakable bones stay broken after dismounting, even once they're back within endurance
+        for frame in dismount_frame..=58 {
+            let bones = engine.view_entity_bones(frame, entity_id).unwrap();
+            assert!(!bones.get(&broken_bone_id).unwrap().intact());
+        }
+
+        let initial_joint_angles = engine.view_entity_joint_angles(0, entity_id).unwrap();
+        assert_eq!(initial_joint_angles.len(), 2);
+        assert!(initial_joint_angles.values().all(|angle| *angle > 0.0));
+
+        // The sled breaks once its joint bends past zero
+        let (sled_break_frame, sled_joint_id) = (dismount_frame..=58)
+            .find_map(|frame| {
+                engine
+                    .view_frame(frame)
+                    .pop()
+                    .unwrap()
+                    .events()
+                    .iter()
+                    .find_map(|event| match event {
+                        EntityEvent::SledBreak(joint_id) => Some((frame, *joint_id)),
+                        _ => None,
+                    })
+            })
+            .unwrap();
+        let joint_angles = engine
+            .view_entity_joint_angles(sled_break_frame, entity_id)
+            .unwrap();
+        assert!(*joint_angles.get(&sled_joint_id).unwrap() < 0.0);
+
+        engine.remove_entity(entity_id).unwrap();
+        assert!(engine.view_entity_bones(0, entity_id).is_none());
+        assert!(engine.view_entity_joint_angles(0, entity_id).is_none());
+        assert!(engine.get_entity_point_id(entity_id, "peg").is_none());
+    }
+
+    #[test]
+    fn skeleton_graph() {
+        let template = EntityTemplateBuilder::default_rider(RemountVersion::ComV2);
+        assert_eq!(template.sled_points().len(), 4);
+        assert_eq!(template.mount_joints().len(), 1);
+
+        // Rider added before its sled, so the sled isn't the first points
+        let mut builder = EntityTemplateBuilder::new().remount_version(RemountVersion::ComV2);
+        let butt = builder.add_point(EntityPointBuilder::new(Point::new(5.0, 0.0)));
+        let shoulder = builder.add_point(EntityPointBuilder::new(Point::new(5.0, -5.5)));
+        let peg = builder.add_point(EntityPointBuilder::new(Point::new(0.0, 0.0)));
+        let tail = builder.add_point(EntityPointBuilder::new(Point::new(0.0, 5.0)));
+        let nose = builder.add_point(EntityPointBuilder::new(Point::new(15.0, 5.0)));
+        let torso = builder.add_bone(EntityBoneBuilder::new((shoulder, butt)));
+        let sled_back = builder.add_bone(EntityBoneBuilder::new((peg, tail)));
+        let sled_front = builder.add_bone(EntityBoneBuilder::new((nose, peg)));
+        builder.add_bone(EntityBoneBuilder::new((tail, nose)));
+        builder.add_bone(EntityBoneBuilder::new((peg, butt)).endurance(0.057));
+        builder.add_bone(EntityBoneBuilder::new((shoulder, nose)).repel(true));
+        builder.add_joint(EntityJointBuilder::new(sled_back, sled_front));
+        let mount_joint = builder.add_joint(EntityJointBuilder::new(torso, sled_front));
+        let template = builder.build().unwrap();
+
+        assert_eq!(template.sled_points(), [peg, tail, nose]);
+        assert_eq!(template.mount_joints(), [mount_joint]);
+    }
+
+    #[test]
+    fn sled_point_mapping() {
+        let comv1 = EntityTemplateBuilder::default_rider(RemountVersion::ComV1);
+        let comv2 = EntityTemplateBuilder::default_rider(RemountVersion::ComV2);
+        let identity: Vec<_> = comv1
+            .sled_points()
+            .iter()
+            .map(|point| (*point, *point))
+            .collect();
+        assert_eq!(comv1.sled_point_mapping(&comv2), Some(identity));
+
+        let sled = |reversed: bool| {
+            let mut builder = EntityTemplateBuilder::new();
+            let mut positions = vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 5.0),
+                Point::new(15.0, 5.0),
+            ];
+            if reversed {
+                positions.reverse();
+            }
+            let points: Vec<_> = positions
+                .into_iter()
+                .map(|position| builder.add_point(EntityPointBuilder::new(position)))
+                .collect();
+            for (index, point) in points.iter().enumerate() {
+                let next_point = points.get((index + 1) % points.len()).unwrap();
+                builder.add_bone(EntityBoneBuilder::new((*point, *next_point)));
+            }
+            (builder.build().unwrap(), points)
+        };
+        let (template, points) = sled(false);
+        let (reversed_template, reversed_points) = sled(true);
+        let mapping = template.sled_point_mapping(&reversed_template).unwrap();
+        assert_eq!(
+            mapping,
+            zip(points, reversed_points.into_iter().rev()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(template.sled_point_mapping(&comv1), None);
+    }
+
+    #[test]
+    fn cross_template_remount() {
+        let file = fs::read("../fixtures/lr_physics_engine/tests/remount_two_riders.track.json")
+            .expect("Failed to read JSON file");
+        let track = lr_format_json::read(&file).expect("Failed to parse track file");
+        let mut reference_engine = from_track(&track, false);
+
+        // Each rider gets its own template, but they should still swap sleds, including riders
+        // of different remount versions in either order
+        for remount_versions in [
+            [RemountVersion::ComV2, RemountVersion::ComV2],
+            [RemountVersion::ComV1, RemountVersion::ComV2],
+            [RemountVersion::ComV2, RemountVersion::ComV1],
+        ] {
+            let mut engine = engine_with_lines(&track);
+            for (rider, remount_version) in zip(track.riders(), remount_versions) {
+                let template_id = engine.register_entity_template(
+                    EntityTemplateBuilder::default_rider(remount_version),
+                );
+                add_rider(&mut engine, rider, template_id);
+            }
+
+            // Frames of the recorded .com states, ending after both riders remounted
+            for frame in [34, 44, 73, 76, 78, 80, 83] {
+                let states = engine.view_frame(frame);
+                let expected_states = reference_engine.view_frame(frame);
+                for (state, expected_state) in zip(&states, &expected_states) {
+                    assert_eq!(
+                        state.point_positions(),
+                        expected_state.point_positions(),
+                        "frame {frame}"
+                    );
+                    assert_eq!(state.mount_phase(), expected_state.mount_phase());
+                    assert_eq!(state.sled_intact(), expected_state.sled_intact());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rest_lengths_after_offset() {
+        let offset_positions = |offset: Vector2Df, rest_lengths_after_offset: bool| {
+            let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+            let template = EntityTemplateBuilder::from(EntityTemplateBuilder::default_rider(
+                RemountVersion::LRA,
+            ))
+            .rest_lengths_after_offset(rest_lengths_after_offset)
+            .build()
+            .unwrap();
+            let template_id = engine.register_entity_template(template);
+            let entity_id = engine.add_entity(template_id).unwrap();
+            engine.set_entity_initial_offset(entity_id, offset).unwrap();
+            engine.add_line(
+                PhysicsLineBuilder::new(Line::new(
+                    Point::new(-50.0, 10.0).translated_by(offset),
+                    Point::new(200.0, 60.0).translated_by(offset),
+                ))
+                .build(),
+            );
+            first_entity_positions(&mut engine, 80)
+        };
+
+        assert_eq!(
+            offset_positions(Vector2Df::zero(), true),
+            offset_positions(Vector2Df::zero(), false)
+        );
+
+        // Rounding after the offset changes some rest lengths
+        let offset = Vector2Df::new(123.456, 789.012);
+        assert_ne!(
+            offset_positions(offset, true),
+            offset_positions(offset, false)
+        );
+
+        // Opted-in rest lengths are measured between the offset points, so every bone starts at
+        // exactly the same stretch as an entity at the origin
+        let initial_stretch_ratios = |offset: Vector2Df, rest_lengths_after_offset: bool| {
+            let mut engine = PhysicsEngine::new(GridVersion::V6_2);
+            let template = EntityTemplateBuilder::from(EntityTemplateBuilder::default_rider(
+                RemountVersion::LRA,
+            ))
+            .rest_lengths_after_offset(rest_lengths_after_offset)
+            .build()
+            .unwrap();
+            let template_id = engine.register_entity_template(template);
+            let entity_id = engine.add_entity(template_id).unwrap();
+            engine.set_entity_initial_offset(entity_id, offset).unwrap();
+            engine
+                .view_entity_bones(0, entity_id)
+                .unwrap()
+                .values()
+                .map(|bone| bone.stretch_ratio().to_bits())
+                .collect::<Vec<_>>()
+        };
+        let origin_stretch_ratios = initial_stretch_ratios(Vector2Df::zero(), false);
+        // Just below powers of two, so the offset points round coarser than the template's
+        let far_offset = Vector2Df::new(65_530.123, -131_070.456);
+        assert_eq!(
+            initial_stretch_ratios(far_offset, true),
+            origin_stretch_ratios
+        );
+        assert_ne!(
+            initial_stretch_ratios(far_offset, false),
+            origin_stretch_ratios
+        );
+    }
+
     fn compare_states(result: Vec<EntityState>, expected: &EngineTestCaseState) {
         let expected_entities = &expected.entities;
         assert!(
@@ -0,0 +1,47 @@
+use vector2d::Vector2Di;
+
+/// Inclusive range of grid cell positions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellRange {
+    min: Vector2Di,
+    max: Vector2Di,
+}
+
+impl CellRange {
+    pub fn new(corner0: Vector2Di, corner1: Vector2Di) -> CellRange {
+        CellRange {
+            min: Vector2Di::new(corner0.x().min(corner1.x()), corner0.y().min(corner1.y())),
+            max: Vector2Di::new(corner0.x().max(corner1.x()), corner0.y().max(corner1.y())),
+        }
+    }
+
+    pub fn min(&self) -> Vector2Di {
+        self.min
+    }
+
+    pub fn max(&self) -> Vector2Di {
+        self.max
+    }
+
+    /// Whether both ranges share at least one cell
+    pub fn overlaps(&self, other: &CellRange) -> bool {
+        self.min.x() <= other.max.x()
+            && other.min.x() <= self.max.x()
+            && self.min.y() <= other.max.y()
+            && other.min.y() <= self.max.y()
+    }
+
+    /// Returns the smallest range containing both ranges
+    pub fn expanded_to_include(&self, other: &CellRange) -> CellRange {
+        CellRange {
+            min: Vector2Di::new(
+                self.min.x().min(other.min.x()),
+                self.min.y().min(other.min.y()),
+            ),
+            max: Vector2Di::new(
+                self.max.x().max(other.max.x()),
+                self.max.y().max(other.max.y()),
+            ),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use geometry::Point;
 use vector2d::{Vector2Df, Vector2Di};
 
-pub(crate) const CELL_SIZE: f64 = 14.0;
+/// Width and height of a grid cell
+pub const CELL_SIZE: f64 = 14.0;
 
 #[derive(Hash, PartialEq, Eq)]
 pub(crate) struct CellKey(i32);
@@ -1,6 +1,17 @@
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GridVersion {
     V6_0,
     V6_1,
     V6_2,
 }
+
+#[cfg(feature = "lr_format_core")]
+impl From<lr_format_core::GridVersion> for GridVersion {
+    fn from(grid_version: lr_format_core::GridVersion) -> Self {
+        match grid_version {
+            lr_format_core::GridVersion::V6_0 => GridVersion::V6_0,
+            lr_format_core::GridVersion::V6_1 => GridVersion::V6_1,
+            lr_format_core::GridVersion::V6_2 => GridVersion::V6_2,
+        }
+    }
+}
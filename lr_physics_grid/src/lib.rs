@@ -1,6 +1,9 @@
+mod cell_range;
 mod grid_cell;
 mod grid_version;
 mod line_grid;
 
+pub use cell_range::CellRange;
+pub use grid_cell::CELL_SIZE;
 pub use grid_version::GridVersion;
 pub use line_grid::{Grid, GridLineId};
@@ -1,18 +1,40 @@
 use crate::{
-    GridVersion,
+    CellRange, GridVersion,
     grid_cell::{CELL_SIZE, CellKey, GridCell},
 };
 use geometry::{Line, Point};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+};
 use vector2d::Vector2Df;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct GridLineId(u32);
 
+impl GridLineId {
+    /// Raw value of the id, for persisting ids outside of the grid
+    pub fn to_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Restores an id persisted with `to_raw`
+    pub fn from_raw(raw: u32) -> GridLineId {
+        GridLineId(raw)
+    }
+}
+
+impl fmt::Display for GridLineId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 pub struct Grid {
     version: GridVersion,
     cells: HashMap<CellKey, BTreeSet<GridLineId>>,
     lines: BTreeMap<GridLineId, Line>,
+    next_id: u32,
 }
 
 impl Grid {
@@ -21,20 +43,16 @@ impl Grid {
             version,
             cells: HashMap::new(),
             lines: BTreeMap::new(),
+            next_id: 0,
         }
     }
 
     /// Adds a new line to the grid
+    ///
+    /// Ids are never reused, so they keep increasing in the order lines were added
     pub fn add_line(&mut self, endpoints: Line) -> GridLineId {
-        let last_id = self.lines.keys().last();
-
-        let next_id = if let Some(last_id) = last_id {
-            last_id.0 + 1
-        } else {
-            0
-        };
-
-        let id = GridLineId(next_id);
+        let id = GridLineId(self.next_id);
+        self.next_id += 1;
         let cell_positions = self.get_cell_positions_along(&endpoints);
 
         for position in cell_positions {
@@ -47,14 +65,15 @@ impl Grid {
     }
 
     /// Updates a line within the grid by preserving its id, returning the old line
+    ///
+    /// Does nothing if the line doesn't exist
     pub fn update_line(&mut self, id: GridLineId, new_line: Line) -> Option<Line> {
-        let old_line = self.lines.insert(id, new_line);
+        let old_line = self.lines.get_mut(&id)?;
+        let old_line = std::mem::replace(old_line, new_line);
 
-        if let Some(old_line) = old_line {
-            let cell_positions = self.get_cell_positions_along(&old_line);
-            for position in cell_positions {
-                self.unregister(id, &position);
-            }
+        let cell_positions = self.get_cell_positions_along(&old_line);
+        for position in cell_positions {
+            self.unregister(id, &position);
         }
 
         let new_cell_positions = self.get_cell_positions_along(&new_line);
@@ -62,7 +81,7 @@ impl Grid {
             self.register(id, &position);
         }
 
-        old_line
+        Some(old_line)
     }
 
     /// Removes a line from the grid, returning the removed line if it exists
@@ -97,6 +116,30 @@ impl Grid {
         line_ids
     }
 
+    /// Gets the range of cells `get_lines_near_point` reads around the point
+    pub fn get_cells_near_point(&self, point: Point) -> CellRange {
+        let offset = CELL_SIZE * Vector2Df::one();
+        CellRange::new(
+            *GridCell::new(point.translated_by(-offset)).position(),
+            *GridCell::new(point.translated_by(offset)).position(),
+        )
+    }
+
+    /// Gets the range of cells a line would be registered in
+    ///
+    /// Lines only register in cells between the cells of their endpoints,
+    /// so a line is only returned by queries whose range overlaps this one
+    pub fn get_cells_along(&self, endpoints: &Line) -> CellRange {
+        CellRange::new(
+            *GridCell::new(endpoints.p0()).position(),
+            *GridCell::new(endpoints.p1()).position(),
+        )
+    }
+
+    pub fn version(&self) -> GridVersion {
+        self.version
+    }
+
     /// Updates the grid's version by re-registering all lines
     pub fn set_version(&mut self, new_version: GridVersion) {
         self.cells.clear();
@@ -273,12 +316,13 @@ impl Grid {
 mod tests {
     #![allow(clippy::unwrap_used)]
     use crate::{
-        Grid, GridVersion,
+        CellRange, Grid, GridVersion,
         grid_cell::{CELL_SIZE, GridCell},
     };
     use geometry::{Line, Point};
     use serde::Deserialize;
-    use std::fs;
+    use std::{collections::BTreeSet, fs};
+    use vector2d::Vector2Di;
 
     #[derive(Deserialize)]
     struct GridTestCase {
@@ -333,6 +377,21 @@ mod tests {
             !grid.cells.is_empty(),
             "grid should still have cells after removing all lines"
         );
+
+        assert!(
+            grid.update_line(line0_id, line0).is_none(),
+            "removed line should not be updated"
+        );
+        assert!(
+            grid.cells.values().all(BTreeSet::is_empty),
+            "removed line should not be registered by update"
+        );
+
+        let line2_id = grid.add_line(line0);
+        assert!(
+            line2_id != line0_id && line2_id != line1_id,
+            "removed line ids should not be reused"
+        );
     }
 
     #[test]
@@ -368,6 +427,11 @@ mod tests {
             vec![line1_id, line0_id, line1_id, line0_id, line2_id],
             "all lines in 3x3 should be included"
         );
+        assert_eq!(
+            grid.get_cells_near_point(Point::new(17.0, 19.0)),
+            CellRange::new(Vector2Di::new(0, 0), Vector2Di::new(2, 2)),
+            "cell range should cover the 3x3"
+        );
     }
 
     fn run_grid_tests(grid: Grid, data: String) {
@@ -385,6 +449,14 @@ mod tests {
                 "Test '{}' failed",
                 case.name
             );
+            let cell_range = grid.get_cells_along(&line);
+            assert!(
+                grid_cells.iter().all(|cell| {
+                    cell_range.overlaps(&CellRange::new(*cell.position(), *cell.position()))
+                }),
+                "Test '{}' registered outside of its endpoint cells",
+                case.name
+            );
             for (cell_index, cell) in grid_cells.iter().enumerate() {
                 assert!(
                     cell.position().x() == case.expected.get(cell_index).unwrap().0